@@ -33,6 +33,22 @@ let logging = Logging::<f64>::default().into_arcx();
 let sink = Terminator::<_>::new(logging.clone());
 ```
 
+The data sent by an output of an [Actor] can also be recorded with a [Probe], leaving the clients and the connections of the model unchanged.
+
+The client methods are called from the tokio worker threads that also drive the other actors.
+Clients with blocking I/O or long computations are given to an [Actor] built with [Actor::blocking],
+their methods being then called from the tokio threads dedicated to blocking operations.
+
+Chains of actors with the same rate can be stepped one after the other in a single task with a [Fused] block,
+the outputs [linked](Actor::link) to the inputs of the next actors of the block being read directly by their clients.
+
+Sporadic events, like operator commands or fault flags, are handled by a [Reactor] that reacts to the data of any of its inputs
+instead of waiting for all of them.
+
+The data of slow or optional producers is read into the client of a faster [Actor] without a [Sampler],
+by setting the [InputMode](crate::InputMode) of the input with [Actor::input_mode]:
+the client keeps the last value it has read when no new data is available.
+
 [client]: crate::clients
 [Mutex]: tokio::sync::Mutex
 [Arc]: std::sync::Arc
@@ -49,6 +65,51 @@ pub(crate) mod plain;
 pub use plain::PlainActor;
 mod task;
 pub use task::Task;
+mod dyn_actor;
+pub use dyn_actor::DynActor;
+mod fused;
+pub use fused::{Fused, LinkPort};
+mod reactor;
+pub use reactor::Reactor;
+#[cfg(feature = "checkpoint")]
+mod checkpoint;
+#[cfg(feature = "checkpoint")]
+pub(crate) use checkpoint::Codec;
+mod monitor;
+mod probe;
+pub(crate) use probe::ProbeObject;
+pub use probe::Probe;
+#[doc(hidden)]
+pub use monitor::Monitor;
+pub use monitor::{Fill, Wait};
+pub(crate) use monitor::{Channel, Gauge, Phase};
+
+/// Greatest common divisor of 2 rates
+pub(crate) fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Runs `f` on the tokio threads dedicated to blocking operations
+///
+/// A panic of `f` is resumed on the calling task.
+/// Outside of a tokio runtime, as for a model run in lockstep, `f` is run on the calling thread
+pub(crate) async fn offload<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let Ok(handle) = tokio::runtime::Handle::try_current() else {
+        return f();
+    };
+    match handle.spawn_blocking(f).await {
+        Ok(r) => r,
+        Err(e) => std::panic::resume_unwind(e.into_panic()),
+    }
+}
 
 /// Type alias for an actor without outputs
 pub type Terminator<C, const NI: usize = 1> = Actor<C, NI, 0>;
@@ -1,15 +1,24 @@
-use super::plain::{PlainActor, IO};
+use super::{
+    plain::{PlainActor, IO},
+    Channel, Monitor, Phase, Wait,
+};
 use crate::{
-    io::{Input, InputObject, OutputObject},
-    ActorOutputBuilder, Result, Who,
+    io::{Clock, Input, InputObject, OutputObject},
+    model::Control,
+    ActorError, ActorOutputBuilder, InputMode, Result, Who,
 };
-use crate::{Data, Read, UniqueIdentifier, Update};
+use crate::{Assoc, Data, Read, UniqueIdentifier, Update};
 use futures::future::join_all;
 use std::{
+    any::type_name,
+    collections::hash_map::DefaultHasher,
     fmt::{self, Debug},
+    hash::{Hash, Hasher},
     sync::Arc,
 };
 use tokio::sync::Mutex;
+#[cfg(feature = "tracing")]
+use tracing::Instrument;
 
 /// Actor model implementation
 pub struct Actor<C, const NI: usize = 1, const NO: usize = 1>
@@ -21,6 +30,30 @@ where
     pub(crate) client: Arc<Mutex<C>>,
     name: Option<String>,
     image: Option<String>,
+    /// Names of the nested sub-systems the actor belongs to
+    pub(super) subsystem: Vec<String>,
+    pub(super) control: Option<Control>,
+    pub(super) monitor: Arc<Monitor>,
+    /// Inputs and outputs channels registered with the monitor
+    channels: (Vec<Channel>, Vec<Channel>),
+    /// Sampling frequency of the model in Hz
+    pub(super) sampling_frequency: Option<f64>,
+    /// Set if the stamps of the inputs data must match
+    aligned: bool,
+    /// Set if the client methods are called from the threads for blocking operations
+    pub(super) blocking: bool,
+    /// Set if the actor is stepped within a [Fused](super::Fused) block
+    pub(super) fused: bool,
+    /// Hashes of the inputs fed directly by the outputs of other actors
+    pub(super) linked: Vec<u64>,
+    #[cfg(feature = "checkpoint")]
+    pub(super) checkpointer: Option<super::checkpoint::Checkpointer<C>>,
+    /// Inputs already received when the actor was checkpointed
+    #[cfg(feature = "checkpoint")]
+    pub(super) received: Vec<u64>,
+    /// Set if the actor is resumed from a checkpoint
+    #[cfg(feature = "checkpoint")]
+    pub(super) resumed: bool,
 }
 
 impl<C, const NI: usize, const NO: usize> From<&Actor<C, NI, NO>> for PlainActor
@@ -42,6 +75,7 @@ where
                 .map(|outputs| outputs.iter().map(|o| IO::from(o)).collect()),
             hash: 0,
             image: actor.image.as_ref().cloned(),
+            subsystem: actor.subsystem.clone(),
         }
     }
 }
@@ -130,6 +164,21 @@ where
             client,
             name: None,
             image: None,
+            subsystem: Vec::new(),
+            control: None,
+            monitor: Default::default(),
+            channels: Default::default(),
+            sampling_frequency: None,
+            aligned: false,
+            blocking: false,
+            fused: false,
+            linked: Vec::new(),
+            #[cfg(feature = "checkpoint")]
+            checkpointer: None,
+            #[cfg(feature = "checkpoint")]
+            received: Vec::new(),
+            #[cfg(feature = "checkpoint")]
+            resumed: false,
         }
     }
     pub fn name<S: Into<String>>(self, name: S) -> Self {
@@ -144,36 +193,315 @@ where
             ..self
         }
     }
+    /// Checks that the inputs data have been produced at the same simulation step
+    ///
+    /// At each cycle of the actor, the [Stamp](crate::interface::Stamp)s of the data
+    /// received on all the inputs are compared and the actor loop ends with
+    /// [ActorError::Misaligned](crate::ActorError::Misaligned) if the steps differ.
+    /// The inputs get out of step when some data is dropped by an [Overflow](crate::Overflow) policy
+    /// or when the data comes from another model.
+    pub fn aligned_inputs(self) -> Self {
+        Self {
+            aligned: true,
+            ..self
+        }
+    }
+    /// Sets the [InputMode] of the input `U`
+    ///
+    /// Only the first input `U` is set if there are several,
+    /// use [into_input_with_mode](crate::TryIntoInputs::into_input_with_mode) to set each of them.
+    /// An error is returned if the actor has no input `U`
+    pub fn input_mode<U>(&mut self, mode: InputMode) -> Result<&mut Self>
+    where
+        C: 'static + Read<U>,
+        U: 'static + Send + Sync + UniqueIdentifier,
+        Assoc<U>: Send + Sync,
+    {
+        let who = self.who();
+        let input = self
+            .inputs
+            .iter_mut()
+            .flatten()
+            .find_map(|input| {
+                input
+                    .as_mut_any()
+                    .downcast_mut::<Input<C, Assoc<U>, U, NI>>()
+            })
+            .ok_or_else(|| ActorError::NoInput(type_name::<U>().to_string(), who))?;
+        input.set_mode(mode);
+        Ok(self)
+    }
+    /// Calls the client methods from the tokio threads dedicated to blocking operations
+    ///
+    /// The [Update], [Read] and [Write](crate::interface::Write) methods of clients
+    /// with blocking I/O or long computations then no longer stall the threads running
+    /// the other actors.
+    /// ```
+    /// # tokio_test::block_on(async {
+    /// use gmt_dos_actors::prelude::*;
+    /// use gmt_dos_clients::{interface::UID, Logging, Signals};
+    /// #[derive(UID)]
+    /// enum Sig {};
+    /// let mut source: Initiator<_> = Actor::from(Signals::new(1, 100)).blocking();
+    /// let logging = Logging::<f64>::default().into_arcx();
+    /// let mut sink = Terminator::<_>::new(logging.clone()).blocking();
+    /// source.add_output().build::<Sig>().into_input(&mut sink)?;
+    ///
+    /// model!(source, sink).check()?.run().await?;
+    /// assert_eq!(logging.lock().await.len(), 100);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn blocking(self) -> Self {
+        Self {
+            blocking: true,
+            ..self
+        }
+    }
     /// Returns a pointer to the actor's client
     pub fn client(&self) -> Arc<Mutex<C>> {
         Arc::clone(&self.client)
     }
+    /// Sets the time stamping of the outputs data with the given inputs and outputs rates
+    ///
+    /// The outputs of a resumed actor carry on from the number of data
+    /// sent before the actor was checkpointed
+    pub(super) fn set_clocks(&mut self, ni: usize, no: usize) {
+        let mut hasher = DefaultHasher::new();
+        self.who().hash(&mut hasher);
+        let clock = Clock {
+            producer: hasher.finish(),
+            rate: no,
+            sampling_frequency: self.sampling_frequency,
+        };
+        #[cfg(feature = "checkpoint")]
+        let resumed = self.resumed;
+        #[cfg(not(feature = "checkpoint"))]
+        let resumed = false;
+        let n_step = self.monitor.n_step();
+        let has_inputs = self.inputs.is_some();
+        if let Some(outputs) = self.outputs.as_mut() {
+            for output in outputs.iter_mut() {
+                let n_sent = if resumed {
+                    let n_bootstrap = match (output.bootstrap(), no >= ni) {
+                        (false, _) => 0,
+                        (true, true) => 1,
+                        (true, false) => ni / no,
+                    };
+                    n_step * if has_inputs { ni } else { no } / no + n_bootstrap
+                } else {
+                    0
+                };
+                output.set_clock(clock, n_sent);
+            }
+        }
+    }
+    /// Hands over the client methods called by the inputs and outputs
+    /// to the threads dedicated to blocking operations
+    pub(super) fn set_blocking(&mut self) {
+        self.inputs
+            .iter_mut()
+            .flatten()
+            .for_each(|input| input.set_blocking());
+        self.outputs
+            .iter_mut()
+            .flatten()
+            .for_each(|output| output.set_blocking());
+    }
     /// Gathers all the inputs from other [Actor] outputs
     pub(super) async fn collect(&mut self) -> Result<&mut Self> {
-        if let Some(inputs) = &mut self.inputs {
-            let futures: Vec<_> = inputs.iter_mut().map(|input| input.recv()).collect();
-            join_all(futures)
-                .await
-                .into_iter()
-                .collect::<Result<Vec<_>>>()?;
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!("collect", actor = %self.who());
+        let timer = self.monitor.timer();
+        // inputs received before the actor was checkpointed are skipped on resume
+        #[cfg(feature = "checkpoint")]
+        let received = std::mem::take(&mut self.received);
+        #[cfg(not(feature = "checkpoint"))]
+        let received: Vec<u64> = Vec::new();
+        // the channels are registered on the first cycle, once the monitor of a fused block is shared
+        if self.channels.0.is_empty() {
+            self.channels.0 = self
+                .inputs
+                .iter()
+                .flatten()
+                .map(|input| {
+                    let wait = Wait::Recv {
+                        uid: input.who(),
+                        hash: input.get_hash(),
+                        fill: input.fill(),
+                    };
+                    self.monitor.register(wait, input.gauge())
+                })
+                .collect();
+        }
+        let (inputs, channels, monitor) = (&mut self.inputs, &self.channels.0, &self.monitor);
+        let collect = async move {
+            if let Some(inputs) = inputs {
+                // the actor waits on all the inputs before receiving any of them
+                let waits: Vec<_> = inputs
+                    .iter_mut()
+                    .zip(channels)
+                    .filter(|(input, _)| !received.contains(&input.get_hash()))
+                    .map(|(input, channel)| {
+                        monitor.wait_on(channel);
+                        (input, channel)
+                    })
+                    .collect();
+                let futures: Vec<_> = waits
+                    .into_iter()
+                    .map(|(input, channel)| async move {
+                        #[cfg(feature = "tracing")]
+                        let span = tracing::info_span!("recv", uid = %input.who());
+                        let recv = input.recv();
+                        #[cfg(feature = "tracing")]
+                        let recv = recv.instrument(span);
+                        let result = recv.await;
+                        // the data is counted before the wait ends, as the checkpoints rely on it
+                        if let Ok(n) = result {
+                            monitor.received(n);
+                        }
+                        monitor.done(channel);
+                        result
+                    })
+                    .collect();
+                join_all(futures)
+                    .await
+                    .into_iter()
+                    .collect::<Result<Vec<_>>>()?;
+            }
+            Ok::<(), crate::ActorError>(())
+        };
+        #[cfg(feature = "tracing")]
+        let collect = collect.instrument(span);
+        collect.await?;
+        if self.aligned {
+            self.check_alignment()?;
         }
+        self.monitor.record(Phase::Inputs, timer);
         Ok(self)
     }
+    /// Checks that all the stamped inputs data have been produced at the same step
+    ///
+    /// The inputs that do not wait for their data are not checked
+    fn check_alignment(&self) -> Result<()> {
+        let stamps: Vec<_> = self
+            .inputs
+            .iter()
+            .flatten()
+            .filter(|input| input.mode() == InputMode::Wait)
+            .filter_map(|input| input.stamp().map(|stamp| (input.who(), stamp.step)))
+            .collect();
+        if stamps.windows(2).any(|w| w[0].1 != w[1].1) {
+            let steps: Vec<_> = stamps
+                .into_iter()
+                .map(|(uid, step)| format!("{uid}@{step}"))
+                .collect();
+            return Err(crate::ActorError::Misaligned(steps.join(", ")));
+        }
+        Ok(())
+    }
+    /// Updates the client
+    pub(super) async fn update(&mut self) -> &mut Self
+    where
+        C: 'static,
+    {
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!("update", actor = %self.who());
+        if self.blocking {
+            let (client, monitor) = (Arc::clone(&self.client), Arc::clone(&self.monitor));
+            super::offload(move || {
+                let mut client = client.blocking_lock();
+                #[cfg(feature = "tracing")]
+                let _span = span.entered();
+                let timer = monitor.timer();
+                client.update();
+                monitor.record(Phase::Update, timer);
+            })
+            .await;
+        } else {
+            let mut client = self.client.lock().await;
+            #[cfg(feature = "tracing")]
+            let _span = span.entered();
+            let timer = self.monitor.timer();
+            client.update();
+            self.monitor.record(Phase::Update, timer);
+        }
+        // the steps of the actors of a fused block are counted by the block
+        if !self.fused {
+            self.monitor.step();
+        }
+        self
+    }
     /// Sends the outputs to other [Actor] inputs
     pub(super) async fn distribute(&mut self) -> Result<&mut Self> {
-        if let Some(outputs) = &mut self.outputs {
-            let futures: Vec<_> = outputs.iter_mut().map(|output| output.send()).collect();
-            join_all(futures)
-                .await
-                .into_iter()
-                .collect::<Result<Vec<_>>>()?;
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!("distribute", actor = %self.who());
+        let timer = self.monitor.timer();
+        if self.channels.1.is_empty() {
+            self.channels.1 = self
+                .outputs
+                .iter()
+                .flatten()
+                .map(|output| {
+                    let wait = Wait::Send {
+                        uid: output.who(),
+                        hash: output.get_hash(),
+                        fill: output.fill(),
+                    };
+                    self.monitor.register(wait, output.gauge())
+                })
+                .collect();
         }
+        let (outputs, channels, monitor) = (&mut self.outputs, &self.channels.1, &self.monitor);
+        let distribute = async move {
+            if let Some(outputs) = outputs {
+                let futures: Vec<_> = outputs
+                    .iter_mut()
+                    .zip(channels)
+                    .map(|(output, channel)| async move {
+                        #[cfg(feature = "tracing")]
+                        let span = tracing::info_span!("send", uid = %output.who());
+                        monitor.wait_on(channel);
+                        let dropped = output.dropped();
+                        let send = output.send();
+                        #[cfg(feature = "tracing")]
+                        let send = send.instrument(span);
+                        let result = send.await;
+                        monitor.done(channel);
+                        if result.is_ok() {
+                            // the dropped messages are never received
+                            let dropped = output.dropped() - dropped;
+                            monitor.sent(output.len() - dropped);
+                            monitor.dropped(dropped);
+                        }
+                        result
+                    })
+                    .collect();
+                join_all(futures)
+                    .await
+                    .into_iter()
+                    .collect::<Result<Vec<_>>>()?;
+            }
+            Ok::<(), crate::ActorError>(())
+        };
+        #[cfg(feature = "tracing")]
+        let distribute = distribute.instrument(span);
+        distribute.await?;
+        self.monitor.record(Phase::Outputs, timer);
         Ok(self)
     }
     /// Invokes outputs senders
-    pub(super) async fn bootstrap(&mut self) -> Result<&mut Self> {
+    pub(super) async fn bootstrap(&mut self, ni: usize, no: usize) -> Result<&mut Self> {
+        // the bootstrapped data of a resumed actor has already been sent
+        #[cfg(feature = "checkpoint")]
+        if self.resumed {
+            return Ok(self);
+        }
         if let Some(outputs) = &mut self.outputs {
-            async fn inner(outputs: &mut Vec<Box<dyn OutputObject>>) -> Result<()> {
+            async fn inner(
+                outputs: &mut Vec<Box<dyn OutputObject>>,
+                monitor: &Monitor,
+            ) -> Result<()> {
                 let futures: Vec<_> = outputs
                     .iter_mut()
                     .filter(|output| output.bootstrap())
@@ -183,7 +511,16 @@ where
                             None::<&dyn std::error::Error>,
                         )
                     })
-                    .map(|output| output.send())
+                    .map(|output| async move {
+                        let dropped = output.dropped();
+                        let result = output.send().await;
+                        if result.is_ok() {
+                            let dropped = output.dropped() - dropped;
+                            monitor.sent(output.len() - dropped);
+                            monitor.dropped(dropped);
+                        }
+                        result
+                    })
                     .collect();
                 join_all(futures)
                     .await
@@ -191,11 +528,11 @@ where
                     .collect::<Result<Vec<_>>>()?;
                 Ok(())
             }
-            if NO >= NI {
-                inner(outputs).await?;
+            if no >= ni {
+                inner(outputs, &self.monitor).await?;
             } else {
-                for _ in 0..NI / NO {
-                    inner(outputs).await?;
+                for _ in 0..ni / no {
+                    inner(outputs, &self.monitor).await?;
                 }
             }
         }
@@ -218,7 +555,21 @@ where
         T: 'static + Send + Sync,
         U: 'static + Send + Sync + UniqueIdentifier<DataType = T>,
     {
-        let input: Input<C, T, U, NI> = Input::new(rx, self.client.clone(), hash);
+        self.add_input_with_mode(rx, hash, InputMode::Wait)
+    }
+    /// Adds an input with the [InputMode] `mode` to an actor
+    pub(crate) fn add_input_with_mode<T, U>(
+        &mut self,
+        rx: flume::Receiver<Data<U>>,
+        hash: u64,
+        mode: InputMode,
+    ) where
+        C: Read<U>,
+        T: 'static + Send + Sync,
+        U: 'static + Send + Sync + UniqueIdentifier<DataType = T>,
+    {
+        let mut input: Input<C, T, U, NI> = Input::new(rx, self.client.clone(), hash);
+        input.set_mode(mode);
         if let Some(ref mut inputs) = self.inputs {
             inputs.push(Box::new(input));
         } else {
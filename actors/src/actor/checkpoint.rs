@@ -0,0 +1,148 @@
+use crate::{
+    interface::{Assoc, Read, UniqueIdentifier},
+    io::{Input, S},
+    model::{ActorSnapshot, CheckpointError, ClientState, InputQueue},
+    Actor, ActorError, Result, Update, Who,
+};
+use gmt_dos_clients::interface::Checkpoint;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{any::type_name, sync::Arc};
+
+/// Serialization of the state of a client that implements [Checkpoint]
+pub(crate) struct Checkpointer<C> {
+    save: fn(&C) -> bincode::Result<Vec<u8>>,
+    restore: fn(&mut C, &[u8]) -> bincode::Result<()>,
+}
+impl<C: Checkpoint> Checkpointer<C> {
+    fn new() -> Self {
+        Self {
+            save: |client| bincode::serialize(&client.state()),
+            restore: |client, bytes| {
+                client.restore(bincode::deserialize(bytes)?);
+                Ok(())
+            },
+        }
+    }
+}
+
+/// Serialization of the data queued on an input
+pub(crate) struct Codec<U: UniqueIdentifier> {
+    pub(crate) save: fn(&S<U>) -> bincode::Result<Vec<u8>>,
+    pub(crate) restore: fn(&[u8]) -> bincode::Result<S<U>>,
+}
+impl<U> Codec<U>
+where
+    U: UniqueIdentifier,
+    Assoc<U>: Serialize + DeserializeOwned,
+{
+    fn new() -> Self {
+        Self {
+            save: |data| bincode::serialize(data),
+            restore: |bytes| bincode::deserialize(bytes),
+        }
+    }
+}
+impl<U: UniqueIdentifier> Clone for Codec<U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<U: UniqueIdentifier> Copy for Codec<U> {}
+
+impl<C, const NI: usize, const NO: usize> Actor<C, NI, NO>
+where
+    C: Update + Send,
+{
+    /// Saves the state of the client in the checkpoints of the model
+    pub fn checkpoint(mut self) -> Self
+    where
+        C: Checkpoint,
+    {
+        self.checkpointer = Some(Checkpointer::new());
+        self
+    }
+    /// Saves the data queued on the inputs `U` in the checkpoints of the model
+    ///
+    /// The data sent to the actor but not yet received at a checkpoint,
+    /// like the data of a bootstrapped output in a feedback loop,
+    /// is serialized into the snapshot and it is received first when the model is resumed.
+    /// An error is returned if the actor has no input `U`
+    pub fn checkpoint_input<U>(&mut self) -> Result<&mut Self>
+    where
+        C: 'static + Read<U>,
+        U: 'static + Send + Sync + UniqueIdentifier,
+        Assoc<U>: Send + Sync + Serialize + DeserializeOwned,
+    {
+        let who = self.who();
+        let mut inputs = self
+            .inputs
+            .iter_mut()
+            .flatten()
+            .filter_map(|input| {
+                input
+                    .as_mut_any()
+                    .downcast_mut::<Input<C, Assoc<U>, U, NI>>()
+            })
+            .peekable();
+        if inputs.peek().is_none() {
+            return Err(ActorError::NoInput(type_name::<U>().to_string(), who));
+        }
+        inputs.for_each(|input| input.set_codec(Codec::new()));
+        Ok(self)
+    }
+}
+
+impl<C, const NI: usize, const NO: usize> Actor<C, NI, NO>
+where
+    C: 'static + Update + Send,
+{
+    /// Returns the serializer of the client state, if the client is checkpointed
+    pub(super) fn client_state(&self) -> Option<ClientState> {
+        self.checkpointer.as_ref().map(|checkpointer| {
+            let (client, save) = (Arc::clone(&self.client), checkpointer.save);
+            let client_state: ClientState = Box::new(move || {
+                let client = Arc::clone(&client);
+                Box::pin(async move { save(&*client.lock().await) })
+            });
+            client_state
+        })
+    }
+    /// Returns the data queues of the inputs
+    pub(super) fn input_queues(&self) -> Vec<Box<dyn InputQueue>> {
+        self.inputs
+            .iter()
+            .flatten()
+            .filter_map(|input| input.queue())
+            .collect()
+    }
+    /// Restores the client state, the inputs queues and the actor loop from a snapshot
+    pub(super) fn restore(
+        &mut self,
+        snapshot: &ActorSnapshot,
+    ) -> std::result::Result<(), CheckpointError> {
+        if let Some(state) = snapshot.state.as_ref() {
+            let checkpointer = self
+                .checkpointer
+                .as_ref()
+                .ok_or_else(|| CheckpointError::NotCheckpointed(Who::who(self)))?;
+            let mut client = self
+                .client
+                .try_lock()
+                .map_err(|_| CheckpointError::Locked(Who::who(self)))?;
+            (checkpointer.restore)(&mut client, state)?;
+        }
+        for (hash, data) in &snapshot.queued {
+            let who = Who::who(self);
+            self.inputs
+                .iter_mut()
+                .flatten()
+                .find(|input| input.get_hash() == *hash)
+                .ok_or(CheckpointError::NoInput(*hash, who))?
+                .restore_queue(data)?;
+        }
+        self.monitor.set_step(snapshot.n_step);
+        self.received = snapshot.received.clone();
+        self.resumed = true;
+        Ok(())
+    }
+}
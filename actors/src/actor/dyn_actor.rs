@@ -0,0 +1,272 @@
+use super::{Monitor, PlainActor, Probe, Task};
+use crate::{
+    interface::{self as io, Assoc, Data, UniqueIdentifier},
+    model::{ActorReport, Control},
+    network::{InputPort, InputsRate, OutputPort},
+    subsystem::PortBuilder,
+    Actor, ActorOutputBuilder, InputMode, Result, Update, Who,
+};
+use async_trait::async_trait;
+use std::{any::Any, fmt::Display, sync::Arc};
+use tokio::sync::Mutex;
+
+/// Actor with inputs and outputs rates set at runtime
+///
+/// A [DynActor] is connected to the outputs and to the inputs of other actors whatever their rates:
+/// the rates at both ends of the connections are checked when the [Model](crate::model::Model) is checked.
+/// ```
+/// # tokio_test::block_on(async {
+/// use gmt_dos_actors::prelude::*;
+/// # use gmt_dos_clients::{interface::UID, Logging, Sampler, Signals};
+/// # #[derive(UID)]
+/// # enum Source {};
+/// let n: usize = "10".parse().unwrap();
+/// let mut source: Initiator<_> = Signals::new(1, 100).into();
+/// let mut sampler = DynActor::new(Sampler::<Vec<f64>, Source>::default().into_arcx(), 1, n);
+/// let logging = Logging::<f64>::default().into_arcx();
+/// let mut sink = DynActor::new(logging.clone(), n, 0);
+/// source.add_output().build::<Source>().into_input(&mut sampler)?;
+/// sampler.add_output().build::<Source>().into_input(&mut sink)?;
+///
+/// model!(source, sampler, sink).check()?.run().await?;
+/// assert_eq!(logging.lock().await.len(), 10);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub struct DynActor<C>
+where
+    C: Update + Send,
+{
+    actor: Actor<C, 1, 1>,
+    ni: usize,
+    no: usize,
+}
+impl<C> DynActor<C>
+where
+    C: Update + Send,
+{
+    /// Creates a new actor with the inputs rate `ni` and the outputs rate `no`
+    pub fn new(client: Arc<tokio::sync::Mutex<C>>, ni: usize, no: usize) -> Self {
+        Self {
+            actor: Actor::new(client),
+            ni,
+            no,
+        }
+    }
+    /// Sets the actor name
+    pub fn name<S: Into<String>>(self, name: S) -> Self {
+        Self {
+            actor: self.actor.name(name),
+            ..self
+        }
+    }
+    /// Calls the client methods from the tokio threads dedicated to blocking operations
+    pub fn blocking(self) -> Self {
+        Self {
+            actor: self.actor.blocking(),
+            ..self
+        }
+    }
+    /// Saves the state of the client in the checkpoints of the model
+    #[cfg(feature = "checkpoint")]
+    pub fn checkpoint(self) -> Self
+    where
+        C: gmt_dos_clients::interface::Checkpoint,
+    {
+        Self {
+            actor: self.actor.checkpoint(),
+            ..self
+        }
+    }
+    /// Sets the actor image
+    pub fn image<S: Into<String>>(self, image: S) -> Self {
+        Self {
+            actor: self.actor.image(image),
+            ..self
+        }
+    }
+    /// Checks that the inputs data have been produced at the same simulation step
+    ///
+    /// See [Actor::aligned_inputs]
+    pub fn aligned_inputs(self) -> Self {
+        Self {
+            actor: self.actor.aligned_inputs(),
+            ..self
+        }
+    }
+    /// Sets the [InputMode] of the input `U`
+    ///
+    /// An error is returned if the actor has no input `U`
+    pub fn input_mode<U>(&mut self, mode: InputMode) -> Result<&mut Self>
+    where
+        C: 'static + io::Read<U>,
+        U: 'static + Send + Sync + UniqueIdentifier,
+        Assoc<U>: Send + Sync,
+    {
+        self.actor.input_mode::<U>(mode)?;
+        Ok(self)
+    }
+    /// Adds an input with the [InputMode] `mode`
+    #[cfg(feature = "loader")]
+    pub(crate) fn add_input_with_mode<U>(
+        &mut self,
+        rx: flume::Receiver<Data<U>>,
+        hash: u64,
+        mode: InputMode,
+    ) where
+        C: 'static + io::Read<U>,
+        U: 'static + Send + Sync + UniqueIdentifier,
+        Assoc<U>: Send + Sync,
+    {
+        self.actor.add_input_with_mode(rx, hash, mode)
+    }
+    /// Saves the data queued on the inputs `U` in the checkpoints of the model
+    ///
+    /// An error is returned if the actor has no input `U`
+    #[cfg(feature = "checkpoint")]
+    pub fn checkpoint_input<U>(&mut self) -> Result<&mut Self>
+    where
+        C: 'static + io::Read<U>,
+        U: 'static + Send + Sync + UniqueIdentifier,
+        Assoc<U>: Send + Sync + serde::Serialize + serde::de::DeserializeOwned,
+    {
+        self.actor.checkpoint_input::<U>()?;
+        Ok(self)
+    }
+    /// Attaches a [Probe] to the output `U`
+    ///
+    /// An error is returned if the actor has no output `U`
+    pub fn probe<U, R>(&mut self, probe: Probe<R>) -> Result<&mut Self>
+    where
+        C: 'static + io::Write<U>,
+        R: 'static + io::Read<U> + Send,
+        U: 'static + Send + Sync + UniqueIdentifier,
+        Assoc<U>: Send + Sync,
+    {
+        self.actor.probe::<U, R>(probe)?;
+        Ok(self)
+    }
+    /// Returns a pointer to the actor's client
+    pub fn client(&self) -> Arc<Mutex<C>> {
+        self.actor.client()
+    }
+    /// Adds a new output
+    pub fn add_output(&mut self) -> PortBuilder<'_, Self> {
+        let name = Who::who(&self.actor);
+        PortBuilder::new(self, name)
+    }
+    /// Returns the inputs rate
+    pub fn inputs_rate(&self) -> usize {
+        self.ni
+    }
+    /// Returns the outputs rate
+    pub fn outputs_rate(&self) -> usize {
+        self.no
+    }
+}
+impl<C> Display for DynActor<C>
+where
+    C: Update + Send,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.actor.fmt(f)
+    }
+}
+
+impl<U, C> InputPort<U> for DynActor<C>
+where
+    Assoc<U>: Send + Sync,
+    U: 'static + Send + Sync + UniqueIdentifier,
+    C: 'static + Update + Send + io::Read<U>,
+{
+    fn add_port(&mut self, rx: flume::Receiver<Data<U>>, hash: u64) {
+        self.actor.add_port(rx, hash)
+    }
+}
+impl<U, C> OutputPort<U> for DynActor<C>
+where
+    Assoc<U>: Send + Sync,
+    U: 'static + Send + Sync + UniqueIdentifier,
+    C: 'static + Update + Send + io::Write<U>,
+{
+    fn build_port(&mut self, builder: ActorOutputBuilder) -> (u64, Vec<flume::Receiver<Data<U>>>) {
+        self.actor.build_port(builder)
+    }
+}
+impl<C, const NI: usize> InputsRate<NI> for DynActor<C> where C: Update + Send {}
+
+#[async_trait]
+impl<C> Task for DynActor<C>
+where
+    C: 'static + Update + Send,
+{
+    fn spawn(mut self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            self.task().await;
+        })
+    }
+    async fn task(&mut self) -> ActorReport {
+        self.actor.run(self.ni, self.no).await
+    }
+    async fn async_run(&mut self) -> Result<()> {
+        self.actor.run_loop(self.ni, self.no).await
+    }
+    fn check_inputs(&self) -> Result<()> {
+        self.actor.check_inputs_rate(self.ni)
+    }
+    fn check_outputs(&self) -> Result<()> {
+        self.actor.check_outputs_rate(self.no)
+    }
+    fn n_inputs(&self) -> usize {
+        self.actor.n_inputs()
+    }
+    fn n_outputs(&self) -> usize {
+        self.actor.n_outputs()
+    }
+    fn inputs_hashes(&self) -> Vec<u64> {
+        self.actor.inputs_hashes()
+    }
+    fn outputs_hashes(&self) -> Vec<u64> {
+        self.actor.outputs_hashes()
+    }
+    fn as_plain(&self) -> PlainActor {
+        PlainActor {
+            inputs_rate: self.ni,
+            outputs_rate: self.no,
+            ..self.actor.as_plain()
+        }
+    }
+    fn set_control(&mut self, control: Control) {
+        self.actor.set_control(control)
+    }
+    fn set_sampling_frequency(&mut self, sampling_frequency: f64) {
+        self.actor.set_sampling_frequency(sampling_frequency)
+    }
+    fn monitor(&self) -> Arc<Monitor> {
+        self.actor.monitor()
+    }
+    fn name(&self) -> String {
+        Who::who(&self.actor)
+    }
+    fn enter_subsystem(&mut self, name: &str) {
+        self.actor.enter_subsystem(name)
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+    #[cfg(feature = "checkpoint")]
+    fn client_state(&self) -> Option<crate::model::ClientState> {
+        self.actor.client_state()
+    }
+    #[cfg(feature = "checkpoint")]
+    fn input_queues(&self) -> Vec<Box<dyn crate::model::InputQueue>> {
+        self.actor.input_queues()
+    }
+    #[cfg(feature = "checkpoint")]
+    fn restore(
+        &mut self,
+        snapshot: &crate::model::ActorSnapshot,
+    ) -> std::result::Result<(), crate::model::CheckpointError> {
+        self.actor.restore(snapshot)
+    }
+}
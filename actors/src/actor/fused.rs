@@ -0,0 +1,413 @@
+use super::{Monitor, PlainActor, Task};
+use crate::{
+    actor::plain::IO,
+    interface::{Assoc, Read, UniqueIdentifier, Write},
+    io::{LinkedInput, Output},
+    model::{ActorReport, Control},
+    Actor, ActorError, ActorOutputBuilder, AddOuput, Result, Update,
+};
+use async_trait::async_trait;
+use std::{any::Any, fmt::Display, sync::Arc};
+use tokio::sync::Mutex;
+
+/// Actors, with an input for the data `U`, that can be linked to the output of another actor
+pub trait LinkPort<U: UniqueIdentifier> {
+    /// Adds an input fed directly by the output with the given hash and returns the reader of the data
+    #[doc(hidden)]
+    fn link_port(&mut self, hash: u64) -> Arc<Mutex<dyn Read<U> + Send>>;
+}
+impl<U, C, const NI: usize, const NO: usize> LinkPort<U> for Actor<C, NI, NO>
+where
+    U: 'static + Send + Sync + UniqueIdentifier,
+    C: 'static + Update + Send + Read<U>,
+{
+    fn link_port(&mut self, hash: u64) -> Arc<Mutex<dyn Read<U> + Send>> {
+        let input = LinkedInput::<U>::new(hash);
+        if let Some(ref mut inputs) = self.inputs {
+            inputs.push(Box::new(input));
+        } else {
+            self.inputs = Some(vec![Box::new(input)]);
+        }
+        self.linked.push(hash);
+        self.client.clone()
+    }
+}
+
+impl<C, const NI: usize, const NO: usize> Actor<C, NI, NO>
+where
+    C: 'static + Update + Send,
+{
+    /// Links the output `U` to the input `U` of another actor
+    ///
+    /// The data written by the client is read by the client of the other actor,
+    /// without a channel in between.
+    /// Both actors must be added to the same [Fused] block, this actor first.
+    pub fn link<U>(&mut self, actor: &mut impl LinkPort<U>) -> &mut Self
+    where
+        C: Write<U>,
+        U: 'static + Send + Sync + UniqueIdentifier,
+        Assoc<U>: Send + Sync,
+    {
+        let Err(output_rx) = (&mut *self, ActorOutputBuilder::new(0)).build::<U>() else {
+            unreachable!("an output always returns its receivers")
+        };
+        let (hash, _) = output_rx.into_parts();
+        let reader = actor.link_port(hash);
+        self.outputs
+            .iter_mut()
+            .flatten()
+            .find_map(|output| {
+                output
+                    .as_mut_any()
+                    .downcast_mut::<Output<C, Assoc<U>, U, NO>>()
+            })
+            .expect("the output has just been built")
+            .reader_push(reader);
+        self
+    }
+}
+
+/// Actor of a [Fused] block
+#[async_trait]
+trait Member: Task {
+    /// Shares the monitor of the block
+    fn fuse(&mut self, monitor: Arc<Monitor>);
+    /// Returns the hashes of the linked inputs
+    fn linked(&self) -> &[u64];
+    /// Returns the hash of each output channel
+    fn channels_hashes(&self) -> Vec<u64>;
+    /// Returns the inputs and outputs rates
+    fn rates(&self) -> (usize, usize);
+    /// Sets the outputs and bootstraps them
+    async fn start(&mut self) -> Result<()>;
+    /// Collects the inputs, updates the client and distributes the outputs
+    async fn step(&mut self) -> Result<()>;
+}
+#[async_trait]
+impl<C, const NI: usize, const NO: usize> Member for Actor<C, NI, NO>
+where
+    C: 'static + Update + Send,
+{
+    fn fuse(&mut self, monitor: Arc<Monitor>) {
+        self.monitor = monitor;
+        self.fused = true;
+    }
+    fn linked(&self) -> &[u64] {
+        &self.linked
+    }
+    fn channels_hashes(&self) -> Vec<u64> {
+        self.outputs
+            .iter()
+            .flatten()
+            .flat_map(|output| vec![output.get_hash(); output.fill().len()])
+            .collect()
+    }
+    fn rates(&self) -> (usize, usize) {
+        (NI, NO)
+    }
+    async fn start(&mut self) -> Result<()> {
+        self.set_clocks(NI, NO);
+        if self.blocking {
+            self.set_blocking();
+        }
+        self.bootstrap(NI, NO).await?;
+        Ok(())
+    }
+    async fn step(&mut self) -> Result<()> {
+        if self.inputs.is_some() {
+            self.collect().await?;
+        }
+        self.update().await;
+        if self.outputs.is_some() {
+            self.distribute().await?;
+        }
+        Ok(())
+    }
+}
+
+/// Actors stepped one after the other in a single task
+///
+/// The actors of a fused block have the same inputs and outputs rates and at each cycle of the block,
+/// each actor collects its inputs, updates its client and distributes its outputs, in the order
+/// the actors have been added to the block.
+/// The outputs [linked](Actor::link) to the inputs of the next actors in the block are read directly
+/// by the clients of these actors, the other outputs and inputs are connected with channels as usual.
+/// The panic of a client is reported as a panic of the block.
+///
+/// In the model [Graph](crate::model::Graph), the actors of a fused block are drawn inside a cluster.
+/// ```
+/// # tokio_test::block_on(async {
+/// use gmt_dos_actors::prelude::*;
+/// use gmt_dos_clients::{interface::UID, Logging, Sampler, Signals};
+/// #[derive(UID)]
+/// enum In {};
+/// #[derive(UID)]
+/// enum Mid {};
+/// #[derive(UID)]
+/// enum Out {};
+///
+/// let mut source: Initiator<_> = Signals::new(1, 100).into();
+/// let mut first: Actor<_> = Sampler::<Vec<f64>, In, Mid>::default().into();
+/// let mut second: Actor<_> = Sampler::<Vec<f64>, Mid, Out>::default().into();
+/// let logging = Logging::<f64>::default().into_arcx();
+/// let mut sink = Terminator::<_>::new(logging.clone());
+/// source.add_output().build::<In>().into_input(&mut first)?;
+/// first.link::<Mid>(&mut second);
+/// second.add_output().build::<Out>().into_input(&mut sink)?;
+/// let relay = Fused::new("Relay").actor(first).actor(second);
+///
+/// model!(source, relay, sink).check()?.run().await?;
+/// assert_eq!(logging.lock().await.len(), 100);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub struct Fused {
+    name: String,
+    subsystem: Vec<String>,
+    members: Vec<Box<dyn Member>>,
+    control: Option<Control>,
+    monitor: Arc<Monitor>,
+}
+impl Fused {
+    /// Creates a new empty fused block
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            subsystem: Vec::new(),
+            members: Vec::new(),
+            control: None,
+            monitor: Default::default(),
+        }
+    }
+    /// Adds an actor to the block
+    pub fn actor<C, const NI: usize, const NO: usize>(mut self, mut actor: Actor<C, NI, NO>) -> Self
+    where
+        C: 'static + Update + Send,
+    {
+        actor.fuse(Arc::clone(&self.monitor));
+        actor.enter_subsystem(&self.name);
+        self.members.push(Box::new(actor));
+        self
+    }
+    /// Returns the rate of the block
+    fn rate(&self) -> usize {
+        self.members
+            .iter()
+            .flat_map(|member| {
+                let (ni, no) = member.rates();
+                [ni, no]
+            })
+            .find(|&rate| rate > 0)
+            .unwrap_or_default()
+    }
+    /// Checks that the actors have the same rates and that the data flows
+    /// from the first actors to the last ones, unless the outputs are bootstrapped
+    fn check_members(&self) -> Result<()> {
+        let rate = self.rate();
+        if self.members.iter().any(|member| {
+            let (ni, no) = member.rates();
+            (ni > 0 && ni != rate) || (no > 0 && no != rate)
+        }) {
+            return Err(ActorError::FusedRates(self.name.clone()));
+        }
+        let plains: Vec<_> = self
+            .members
+            .iter()
+            .map(|member| member.as_plain())
+            .collect();
+        for (j, (member, plain)) in self.members.iter().zip(&plains).enumerate() {
+            for input in plain.inputs.iter().flatten() {
+                let hash = input.get_hash();
+                let producer = plains.iter().enumerate().find_map(|(i, plain)| {
+                    plain
+                        .outputs
+                        .iter()
+                        .flatten()
+                        .find(|output| output.get_hash() == hash)
+                        .map(|output| (i, output))
+                });
+                match producer {
+                    Some((i, output)) if i >= j && !matches!(output, IO::Bootstrap(_)) => {
+                        return Err(ActorError::FusedOrder(
+                            member.name(),
+                            self.members[i].name(),
+                            input.name().to_string(),
+                        ))
+                    }
+                    None if member.linked().contains(&hash) => {
+                        return Err(ActorError::Unfused(member.name()))
+                    }
+                    _ => (),
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Runs the actors cycles
+    async fn run_loop(&mut self) -> Result<()> {
+        let rate = self.rate();
+        for member in self.members.iter_mut() {
+            member.start().await?;
+        }
+        loop {
+            if let Some(control) = self.control.as_mut() {
+                control.proceed(&self.monitor, rate).await?;
+            }
+            for member in self.members.iter_mut() {
+                member.step().await?;
+            }
+            self.monitor.step();
+        }
+    }
+}
+impl Display for Fused {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} (fused) [{}]",
+            self.name.to_uppercase(),
+            self.members.len()
+        )?;
+        for member in &self.members {
+            write!(f, " {}", member)?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Task for Fused {
+    /// Runs the cycles of the block
+    async fn async_run(&mut self) -> Result<()> {
+        self.run_loop().await
+    }
+    fn spawn(mut self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            self.task().await;
+        })
+    }
+    fn check_inputs(&self) -> Result<()> {
+        self.members
+            .iter()
+            .try_for_each(|member| member.check_inputs())?;
+        self.check_members()
+    }
+    fn check_outputs(&self) -> Result<()> {
+        self.members
+            .iter()
+            .try_for_each(|member| member.check_outputs())
+    }
+    /// Runs the cycles of the block
+    ///
+    /// Returns the report on how the cycles ended
+    async fn task(&mut self) -> ActorReport {
+        let (name, monitor) = (self.name.clone(), Arc::clone(&self.monitor));
+        ActorReport::catch(name, &monitor, async {
+            crate::print_info(
+                format!("{} loop started", self.name),
+                None::<&dyn std::error::Error>,
+            );
+            let result = self.run_loop().await;
+            if let Err(e) = result.as_ref() {
+                crate::print_info(format!("{} loop ended", self.name), Some(e));
+            }
+            let mut report = ActorReport::new(self.name.clone(), self.monitor.n_step(), result);
+            report.profile = self.monitor.profile();
+            report.n_dropped = self.monitor.n_dropped();
+            report
+        })
+        .await
+    }
+    fn n_inputs(&self) -> usize {
+        self.members.iter().map(|member| member.n_inputs()).sum()
+    }
+    fn n_outputs(&self) -> usize {
+        self.members.iter().map(|member| member.n_outputs()).sum()
+    }
+    /// Returns the hashes of the inputs with channels
+    fn inputs_hashes(&self) -> Vec<u64> {
+        let linked: Vec<_> = self
+            .members
+            .iter()
+            .flat_map(|member| member.linked().to_vec())
+            .collect();
+        self.members
+            .iter()
+            .flat_map(|member| member.inputs_hashes())
+            .filter(|hash| !linked.contains(hash))
+            .collect()
+    }
+    /// Returns the hashes of the outputs with channels to actors outside of the block
+    fn outputs_hashes(&self) -> Vec<u64> {
+        let mut consumed = self.inputs_hashes();
+        self.members
+            .iter()
+            .flat_map(|member| member.channels_hashes())
+            .filter(|hash| match consumed.iter().position(|h| h == hash) {
+                Some(k) => {
+                    consumed.swap_remove(k);
+                    false
+                }
+                None => true,
+            })
+            .collect()
+    }
+    /// Returns the block collapsed into a single actor
+    fn as_plain(&self) -> PlainActor {
+        PlainActor::collapse(
+            self.name.clone(),
+            self.subsystem.clone(),
+            self.members
+                .iter()
+                .map(|member| member.as_plain())
+                .collect(),
+            &self.outputs_hashes(),
+        )
+    }
+    fn as_plains(&self) -> Vec<PlainActor> {
+        self.members
+            .iter()
+            .map(|member| member.as_plain())
+            .collect()
+    }
+    fn set_control(&mut self, control: Control) {
+        self.control = Some(control);
+    }
+    fn set_sampling_frequency(&mut self, sampling_frequency: f64) {
+        self.members
+            .iter_mut()
+            .for_each(|member| member.set_sampling_frequency(sampling_frequency))
+    }
+    fn monitor(&self) -> Arc<Monitor> {
+        Arc::clone(&self.monitor)
+    }
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+    fn enter_subsystem(&mut self, name: &str) {
+        self.subsystem.insert(0, name.to_string());
+        self.members
+            .iter_mut()
+            .for_each(|member| member.enter_subsystem(name));
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+    #[cfg(feature = "checkpoint")]
+    fn client_state(&self) -> Option<crate::model::ClientState> {
+        None
+    }
+    #[cfg(feature = "checkpoint")]
+    fn input_queues(&self) -> Vec<Box<dyn crate::model::InputQueue>> {
+        Vec::new()
+    }
+    #[cfg(feature = "checkpoint")]
+    fn restore(
+        &mut self,
+        _snapshot: &crate::model::ActorSnapshot,
+    ) -> std::result::Result<(), crate::model::CheckpointError> {
+        Err(crate::model::CheckpointError::NotCheckpointed(
+            self.name.clone(),
+        ))
+    }
+}
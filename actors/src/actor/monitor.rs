@@ -0,0 +1,291 @@
+use std::{
+    fmt::Display,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+#[cfg(feature = "checkpoint")]
+use tokio::sync::futures::Notified;
+use tokio::sync::Notify;
+
+use crate::model::{Profile, Timing};
+
+/// Channel fill level: # of messages and capacity (`None` if unbounded)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fill(pub usize, pub Option<usize>);
+impl Display for Fill {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.1 {
+            Some(capacity) => write!(f, "{}/{}", self.0, capacity),
+            None => write!(f, "{}/inf", self.0),
+        }
+    }
+}
+
+/// Channel an actor is waiting on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Wait {
+    /// Waiting for the data of an input
+    Recv { uid: String, hash: u64, fill: Fill },
+    /// Waiting for the data of an output to be received, one fill level per receiver
+    Send {
+        uid: String,
+        hash: u64,
+        fill: Vec<Fill>,
+    },
+    /// Waiting for the model to be resumed
+    Paused,
+}
+impl Display for Wait {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Wait::Recv { uid, fill, .. } => write!(f, "receiving {uid} [{fill}]"),
+            Wait::Send { uid, fill, .. } => {
+                let fill: Vec<_> = fill.iter().map(|fill| fill.to_string()).collect();
+                write!(f, "sending {uid} [{}]", fill.join(", "))
+            }
+            Wait::Paused => write!(f, "paused"),
+        }
+    }
+}
+impl Wait {
+    /// Returns the wait with the current fill levels of its channels
+    fn refresh(&self, gauge: &Gauge) -> Self {
+        match self {
+            Wait::Recv { uid, hash, .. } => Wait::Recv {
+                uid: uid.clone(),
+                hash: *hash,
+                fill: gauge.0().first().copied().unwrap_or(Fill(0, None)),
+            },
+            Wait::Send { uid, hash, .. } => Wait::Send {
+                uid: uid.clone(),
+                hash: *hash,
+                fill: gauge.0(),
+            },
+            Wait::Paused => Wait::Paused,
+        }
+    }
+}
+
+/// Live fill levels of the channels of a [Wait]
+///
+/// A gauge holds a handle to the channels and is kept until the actor task ends
+pub(crate) struct Gauge(Box<dyn Fn() -> Vec<Fill> + Send + Sync>);
+impl Gauge {
+    pub(crate) fn new<F>(fill: F) -> Self
+    where
+        F: Fn() -> Vec<Fill> + Send + Sync + 'static,
+    {
+        Self(Box::new(fill))
+    }
+}
+impl std::fmt::Debug for Gauge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Gauge").field(&self.0()).finish()
+    }
+}
+
+/// Channel of an actor registered with its [Monitor]
+///
+/// The channel is flagged while the actor is waiting on it
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Channel(Arc<AtomicBool>);
+
+/// Phases of the actor loop
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Phase {
+    Inputs,
+    Update,
+    Outputs,
+}
+
+/// Time accumulator of a phase of the actor loop
+#[derive(Debug, Default)]
+struct Timer {
+    n: AtomicUsize,
+    total: AtomicU64,
+    max: AtomicU64,
+}
+impl Timer {
+    fn record(&self, elapsed: Duration) {
+        let nanos = elapsed.as_nanos() as u64;
+        self.n.fetch_add(1, Ordering::Relaxed);
+        self.total.fetch_add(nanos, Ordering::Relaxed);
+        self.max.fetch_max(nanos, Ordering::Relaxed);
+    }
+    fn timing(&self) -> Timing {
+        let n = self.n.load(Ordering::Relaxed);
+        let total = self.total.load(Ordering::Relaxed);
+        Timing {
+            n,
+            mean: Duration::from_nanos(total.checked_div(n as u64).unwrap_or_default()),
+            max: Duration::from_nanos(self.max.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+/// Actor loop state shared with the [Model](crate::model::Model)
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct Monitor {
+    step: AtomicUsize,
+    sent: AtomicUsize,
+    received: AtomicUsize,
+    dropped: AtomicUsize,
+    channels: Mutex<Vec<(Wait, Gauge, Channel)>>,
+    paused: AtomicBool,
+    exited: AtomicBool,
+    /// Number of changes of the channels the actor is waiting on
+    changes: AtomicUsize,
+    changed: Notify,
+    /// Notifies the changes, once enabled by a checkpoint
+    watched: AtomicBool,
+    profiling: AtomicBool,
+    timers: [Timer; 3],
+}
+impl Monitor {
+    /// Increments the number of client updates
+    #[inline]
+    pub(crate) fn step(&self) {
+        self.step.fetch_add(1, Ordering::Relaxed);
+    }
+    /// Returns the number of client updates
+    pub fn n_step(&self) -> usize {
+        self.step.load(Ordering::Relaxed)
+    }
+    /// Sets the number of client updates
+    #[cfg(feature = "checkpoint")]
+    pub(crate) fn set_step(&self, n: usize) {
+        self.step.store(n, Ordering::Relaxed);
+    }
+    /// Increments the number of messages sent by `n`
+    #[inline]
+    pub(crate) fn sent(&self, n: usize) {
+        self.sent.fetch_add(n, Ordering::Relaxed);
+    }
+    /// Returns the number of messages sent
+    pub fn n_sent(&self) -> usize {
+        self.sent.load(Ordering::Relaxed)
+    }
+    /// Increments the number of messages dropped by the outputs overflow policies by `n`
+    #[inline]
+    pub(crate) fn dropped(&self, n: usize) {
+        self.dropped.fetch_add(n, Ordering::Relaxed);
+    }
+    /// Returns the number of messages dropped by the outputs overflow policies
+    pub fn n_dropped(&self) -> usize {
+        self.dropped.load(Ordering::Relaxed)
+    }
+    /// Increments the number of messages received by `n`
+    #[inline]
+    pub(crate) fn received(&self, n: usize) {
+        self.received.fetch_add(n, Ordering::Relaxed);
+    }
+    /// Returns the number of messages received
+    pub fn n_received(&self) -> usize {
+        self.received.load(Ordering::Relaxed)
+    }
+    /// Registers a channel the actor may wait on, with the gauge of the channel fill levels
+    ///
+    /// A channel is registered once, before the actor waits on it for the first time
+    pub(crate) fn register(&self, wait: Wait, gauge: Gauge) -> Channel {
+        let channel = Channel::default();
+        self.channels
+            .lock()
+            .unwrap()
+            .push((wait, gauge, channel.clone()));
+        channel
+    }
+    /// Records that the actor starts waiting on a channel
+    #[inline]
+    pub(crate) fn wait_on(&self, channel: &Channel) {
+        channel.0.store(true, Ordering::SeqCst);
+        self.change();
+    }
+    /// Records that the actor is done waiting on a channel
+    #[inline]
+    pub(crate) fn done(&self, channel: &Channel) {
+        channel.0.store(false, Ordering::SeqCst);
+        self.change();
+    }
+    /// Records that the actor is paused or resumed
+    pub(crate) fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::SeqCst);
+        self.change();
+    }
+    /// Records that the actor task has ended
+    ///
+    /// The channels of the actor are forgotten, so the gauges release the channels
+    pub(crate) fn exit(&self) {
+        self.exited.store(true, Ordering::Relaxed);
+        self.channels.lock().unwrap().clear();
+        self.paused.store(false, Ordering::SeqCst);
+        self.change();
+    }
+    /// Counts a change of the channels the actor is waiting on and notifies it if enabled
+    #[inline]
+    fn change(&self) {
+        self.changes.fetch_add(1, Ordering::SeqCst);
+        if self.watched.load(Ordering::SeqCst) {
+            self.changed.notify_waiters();
+        }
+    }
+    /// Returns the number of changes of the channels the actor is waiting on
+    pub fn n_change(&self) -> usize {
+        self.changes.load(Ordering::SeqCst)
+    }
+    /// Enables the notifications of the changes of the channels the actor is waiting on
+    #[cfg(feature = "checkpoint")]
+    pub(crate) fn enable_watching(&self) {
+        self.watched.store(true, Ordering::SeqCst);
+    }
+    /// Checks if the actor task has ended
+    pub fn has_exited(&self) -> bool {
+        self.exited.load(Ordering::Relaxed)
+    }
+    /// Returns a future that completes at the next change of the channels the actor is waiting on
+    /// or when the actor task ends, once the notifications are enabled
+    #[cfg(feature = "checkpoint")]
+    pub(crate) fn changed(&self) -> Notified<'_> {
+        self.changed.notified()
+    }
+    /// Returns the channels the actor is waiting on, with their current fill levels
+    pub fn waits(&self) -> Vec<Wait> {
+        let paused = self
+            .paused
+            .load(Ordering::SeqCst)
+            .then_some(Wait::Paused);
+        let channels = self.channels.lock().unwrap();
+        let waits = channels
+            .iter()
+            .filter(|(_, _, channel)| channel.0.load(Ordering::SeqCst))
+            .map(|(wait, gauge, _)| wait.refresh(gauge));
+        paused.into_iter().chain(waits).collect()
+    }
+    /// Enables the timing of the actor loop phases
+    pub(crate) fn enable_profiling(&self) {
+        self.profiling.store(true, Ordering::Relaxed);
+    }
+    /// Starts timing a phase, if profiling is enabled
+    #[inline]
+    pub(crate) fn timer(&self) -> Option<Instant> {
+        self.profiling.load(Ordering::Relaxed).then(Instant::now)
+    }
+    /// Records the time elapsed since the phase started
+    #[inline]
+    pub(crate) fn record(&self, phase: Phase, timer: Option<Instant>) {
+        if let Some(start) = timer {
+            self.timers[phase as usize].record(start.elapsed());
+        }
+    }
+    /// Returns the timing of the actor loop phases, if profiling is enabled
+    pub fn profile(&self) -> Option<Profile> {
+        self.profiling.load(Ordering::Relaxed).then(|| Profile {
+            inputs: self.timers[Phase::Inputs as usize].timing(),
+            update: self.timers[Phase::Update as usize].timing(),
+            outputs: self.timers[Phase::Outputs as usize].timing(),
+        })
+    }
+}
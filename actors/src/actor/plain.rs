@@ -1,14 +1,31 @@
-use crate::io::{InputObject, OutputObject};
+use crate::{
+    io::{EventObject, InputObject, OutputObject},
+    uid::short_name,
+    InputMode,
+};
 
 #[derive(Debug, Hash)]
 #[doc(hidden)]
 pub struct IOData {
     pub name: String,
+    /// Name of the UID without the module paths
+    pub uid: String,
     pub hash: u64,
+    /// Mode of an input
+    pub mode: InputMode,
 }
 impl IOData {
     pub fn new(name: String, hash: u64) -> Self {
-        Self { name, hash }
+        Self {
+            uid: short_name(&name),
+            name,
+            hash,
+            mode: InputMode::Wait,
+        }
+    }
+    /// Sets the mode of an input
+    pub fn mode(self, mode: InputMode) -> Self {
+        Self { mode, ..self }
     }
 }
 #[derive(Debug, Hash)]
@@ -28,11 +45,81 @@ pub struct PlainActor {
     pub outputs: Option<Vec<IO>>,
     pub hash: u64,
     pub image: Option<String>,
+    /// Names of the nested sub-systems the actor belongs to, from the outermost
+    pub subsystem: Vec<String>,
+}
+
+impl PlainActor {
+    /// Merges the actors of the sub-system `name` into a single actor
+    ///
+    /// The inputs connected to the outputs of the other actors of the sub-system are removed
+    /// and so are the outputs connected only to the inputs of the other actors of the sub-system,
+    /// `inputs_hashes` are the hashes of the inputs of the actors outside of the sub-system
+    pub(crate) fn collapse(
+        name: String,
+        subsystem: Vec<String>,
+        actors: Vec<PlainActor>,
+        inputs_hashes: &[u64],
+    ) -> Self {
+        let produced: Vec<u64> = actors
+            .iter()
+            .flat_map(|actor| {
+                actor
+                    .outputs
+                    .iter()
+                    .flatten()
+                    .map(|output| output.get_hash())
+            })
+            .collect();
+        let consumed: Vec<u64> = actors
+            .iter()
+            .flat_map(|actor| actor.inputs.iter().flatten().map(|input| input.get_hash()))
+            .collect();
+        let (mut inputs_rate, mut outputs_rate) = (0, 0);
+        let mut inputs = vec![];
+        let mut outputs = vec![];
+        for actor in actors {
+            for input in actor.inputs.into_iter().flatten() {
+                if !produced.contains(&input.get_hash()) {
+                    inputs_rate = actor.inputs_rate;
+                    inputs.push(input);
+                }
+            }
+            for output in actor.outputs.into_iter().flatten() {
+                let hash = output.get_hash();
+                if !consumed.contains(&hash) || inputs_hashes.contains(&hash) {
+                    outputs_rate = actor.outputs_rate;
+                    outputs.push(output);
+                }
+            }
+        }
+        Self {
+            client: name,
+            inputs_rate,
+            outputs_rate,
+            inputs: (!inputs.is_empty()).then_some(inputs),
+            outputs: (!outputs.is_empty()).then_some(outputs),
+            hash: 0,
+            image: None,
+            subsystem,
+        }
+    }
 }
 
 impl From<&Box<dyn InputObject>> for IO {
     fn from(value: &Box<dyn InputObject>) -> Self {
+        let data = IOData::new(value.who(), value.get_hash()).mode(value.mode());
         if let Some(_) = value.capacity() {
+            IO::Regular(data)
+        } else {
+            IO::Unbounded(data)
+        }
+    }
+}
+
+impl From<&Box<dyn EventObject>> for IO {
+    fn from(value: &Box<dyn EventObject>) -> Self {
+        if value.capacity().is_some() {
             IO::Regular(IOData::new(value.who(), value.get_hash()))
         } else {
             IO::Unbounded(IOData::new(value.who(), value.get_hash()))
@@ -51,27 +138,47 @@ impl From<&Box<dyn OutputObject>> for IO {
 }
 
 impl IO {
+    /// Returns the hash of the input/output pair
+    pub fn get_hash(&self) -> u64 {
+        match self {
+            IO::Bootstrap(data) | IO::Regular(data) | IO::Unbounded(data) => data.hash,
+        }
+    }
+    /// Returns the name of the input/output data
+    pub fn name(&self) -> &str {
+        match self {
+            IO::Bootstrap(data) | IO::Regular(data) | IO::Unbounded(data) => &data.name,
+        }
+    }
+    /// Checks if the input waits for its data
+    pub fn waits(&self) -> bool {
+        match self {
+            IO::Bootstrap(data) | IO::Regular(data) | IO::Unbounded(data) => {
+                data.mode == InputMode::Wait
+            }
+        }
+    }
     pub fn as_formatted_input(&self, actor_hash: u64, color: usize) -> String {
         match self {
             IO::Bootstrap(input) => format!(
                 r#"{0} -> {1} [label="{2}", color={3}, style=bold];"#,
                 input.hash,
                 actor_hash,
-                input.name.split("::").last().unwrap(),
+                input.uid,
                 color
             ),
             IO::Regular(input) => format!(
                 r#"{0} -> {1} [label="{2}", color={3}];"#,
                 input.hash,
                 actor_hash,
-                input.name.split("::").last().unwrap(),
+                input.uid,
                 color
             ),
             IO::Unbounded(input) => format!(
                 r#"{0} -> {1} [label="{2}", color={3}, style=dashed];"#,
                 input.hash,
                 actor_hash,
-                input.name.split("::").last().unwrap(),
+                input.uid,
                 color
             ),
         }
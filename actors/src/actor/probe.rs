@@ -0,0 +1,133 @@
+use crate::{
+    interface::{Assoc, Entry, Size, Write},
+    io::{Output, OutputObject},
+    ActorError, Data, Read, Result, UniqueIdentifier, Update, Who,
+};
+use async_trait::async_trait;
+use std::{any::type_name, sync::Arc};
+use tokio::sync::Mutex;
+
+/// Output probe
+///
+/// A probe is attached to an existing output of an [Actor](crate::Actor) with [Actor::probe](crate::Actor::probe).
+/// Every data sent by the output is also read by the probe recorder, with an optional decimation,
+/// leaving the clients and the connections of the model unchanged.
+/// ```
+/// # tokio_test::block_on(async {
+/// use gmt_dos_actors::{actor::Probe, prelude::*};
+/// use gmt_dos_clients::{interface::UID, Logging, Sampler, Signals};
+/// #[derive(UID)]
+/// enum In {};
+/// #[derive(UID)]
+/// enum Out {};
+/// let mut source: Initiator<_> = Signals::new(1, 100).into();
+/// let mut sampler: Actor<_> = Sampler::<Vec<f64>, In, Out>::default().into();
+/// let logging = Logging::<f64>::default().into_arcx();
+/// let mut sink = Terminator::<_>::new(logging.clone());
+/// source.add_output().build::<In>().into_input(&mut sampler)?;
+/// sampler.add_output().build::<Out>().into_input(&mut sink)?;
+///
+/// let probe = Logging::<f64>::default().into_arcx();
+/// sampler.probe::<Out, _>(Probe::new(probe.clone()).decimation(10))?;
+///
+/// model!(source, sampler, sink).check()?.run().await?;
+/// assert_eq!(logging.lock().await.len(), 100);
+/// assert_eq!(probe.lock().await.len(), 10);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub struct Probe<R> {
+    recorder: Arc<Mutex<R>>,
+    decimation: usize,
+    count: usize,
+}
+impl<R> Probe<R> {
+    /// Creates a new probe that records the output data into `recorder`
+    pub fn new(recorder: Arc<Mutex<R>>) -> Self {
+        Self {
+            recorder,
+            decimation: 1,
+            count: 0,
+        }
+    }
+    /// Records only one data every `decimation` data sent by the output (default: 1)
+    pub fn decimation(self, decimation: usize) -> Self {
+        assert!(decimation > 0, "the probe decimation must be greater than 0");
+        Self { decimation, ..self }
+    }
+}
+
+/// Recorder of the data sent by an output
+#[async_trait]
+pub(crate) trait ProbeObject<U: UniqueIdentifier>: Send + Sync {
+    async fn record(&mut self, data: &Data<U>);
+}
+#[async_trait]
+impl<R, U> ProbeObject<U> for Probe<R>
+where
+    R: Read<U> + Send,
+    U: 'static + UniqueIdentifier,
+    Assoc<U>: Send + Sync,
+{
+    async fn record(&mut self, data: &Data<U>) {
+        if self.count.is_multiple_of(self.decimation) {
+            (*self.recorder.lock().await).read(data.clone());
+        }
+        self.count += 1;
+    }
+}
+
+impl<C, const NI: usize, const NO: usize> crate::Actor<C, NI, NO>
+where
+    C: 'static + Update + Send,
+{
+    /// Attaches a [Probe] to the output `U`
+    ///
+    /// An error is returned if the actor has no output `U`
+    pub fn probe<U, R>(&mut self, probe: Probe<R>) -> Result<&mut Self>
+    where
+        C: Write<U>,
+        R: 'static + Read<U> + Send,
+        U: 'static + Send + Sync + UniqueIdentifier,
+        Assoc<U>: Send + Sync,
+    {
+        self.probed_output::<U>()?.probe_push(Box::new(probe));
+        Ok(self)
+    }
+    /// Returns the output `U` to probe
+    pub(crate) fn probed_output<U>(&mut self) -> Result<&mut Output<C, Assoc<U>, U, NO>>
+    where
+        C: Write<U>,
+        U: 'static + Send + Sync + UniqueIdentifier,
+        Assoc<U>: Send + Sync,
+    {
+        let who = self.who();
+        let output = self
+            .outputs
+            .iter_mut()
+            .flatten()
+            .find_map(|output| {
+                output
+                    .as_mut_any()
+                    .downcast_mut::<Output<C, Assoc<U>, U, NO>>()
+            })
+            .ok_or_else(|| ActorError::NoProbedOutput(type_name::<U>().to_string(), who))?;
+        log::debug!("{} probed", OutputObject::highlight(output));
+        Ok(output)
+    }
+    /// Attaches a [Probe] to the output `U`, adding first a new entry to the recorder
+    ///
+    /// The size of the entry is given by the actor client, as for [IntoLogs](crate::IntoLogs).
+    /// An error is returned if the actor has no output `U`
+    pub async fn probe_log<U, R>(&mut self, probe: Probe<R>) -> Result<&mut Self>
+    where
+        C: Write<U> + Size<U>,
+        R: 'static + Read<U> + Entry<U> + Send,
+        U: 'static + Send + Sync + UniqueIdentifier,
+        Assoc<U>: Send + Sync,
+    {
+        let size = <C as Size<U>>::len(&*self.client.lock().await);
+        (*probe.recorder.lock().await).entry(size);
+        self.probe(probe)
+    }
+}
@@ -0,0 +1,385 @@
+use super::{Monitor, Phase, PlainActor, Task, Wait};
+use crate::{
+    actor::plain::IO,
+    interface::{self as io, Assoc, Data, ReadEvent, UniqueIdentifier},
+    io::{EventInput, EventObject},
+    model::{ActorReport, Control},
+    network::{InputPort, InputsRate, OutputPort},
+    Actor, ActorError, ActorOutputBuilder, Result, Update, Who,
+};
+use async_trait::async_trait;
+use futures::future::select_all;
+use std::{
+    any::Any,
+    fmt::Display,
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+use tokio::sync::Mutex;
+
+/// Event-driven actor
+///
+/// A [Reactor] waits for the data of any of its inputs instead of all of them.
+/// The data of the first input to receive some is read by the client with [ReadEvent] and,
+/// only if the client reacts to the event, the client is updated and the outputs are sent.
+/// The inputs accept the data at any rate and the reactor loop ends once all the inputs are disconnected.
+///
+/// A [Reactor] dereferences to an [Actor] with the outputs rate `NO`,
+/// so its outputs are added and connected to other actors as for an [Actor].
+/// The inputs added to that [Actor] instead of the reactor, e.g. with [IntoLogs](crate::IntoLogs), are never read
+/// and fail the check of the model.
+/// ```
+/// # tokio_test::block_on(async {
+/// use gmt_dos_actors::{actor::Reactor, prelude::*};
+/// use gmt_dos_clients::{
+///     interface::{Data, ReadEvent, Update, Write, UID},
+///     Logging,
+/// };
+/// #[derive(UID)]
+/// enum Command {}
+/// #[derive(UID)]
+/// enum Mode {}
+///
+/// struct Operator(std::vec::IntoIter<f64>);
+/// impl Update for Operator {}
+/// impl Write<Command> for Operator {
+///     fn write(&mut self) -> Option<Data<Command>> {
+///         self.0.next().map(|command| vec![command].into())
+///     }
+/// }
+/// // switches mode whenever the sign of the command changes
+/// #[derive(Default)]
+/// struct Supervisor {
+///     positive: bool,
+///     n_switch: usize,
+/// }
+/// impl Update for Supervisor {
+///     fn update(&mut self) {
+///         self.n_switch += 1;
+///     }
+/// }
+/// impl ReadEvent<Command> for Supervisor {
+///     fn read_event(&mut self, data: Data<Command>) -> bool {
+///         let positive = data[0] > 0.;
+///         std::mem::replace(&mut self.positive, positive) != positive
+///     }
+/// }
+/// impl Write<Mode> for Supervisor {
+///     fn write(&mut self) -> Option<Data<Mode>> {
+///         Some(vec![self.n_switch as f64].into())
+///     }
+/// }
+///
+/// let mut operator: Initiator<_> = Operator(vec![1., 2., -1., -3., 4.].into_iter()).into();
+/// let mut supervisor: Reactor<_> = Supervisor::default().into();
+/// let logging = Logging::<f64>::default().into_arcx();
+/// let mut sink = Terminator::<_>::new(logging.clone());
+/// operator.add_output().build::<Command>().into_input(&mut supervisor)?;
+/// supervisor.add_output().build::<Mode>().into_input(&mut sink)?;
+///
+/// model!(operator, supervisor, sink).check()?.run().await?;
+/// assert_eq!(logging.lock().await.len(), 3);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub struct Reactor<C, const NO: usize = 1>
+where
+    C: Update + Send,
+{
+    actor: Actor<C, 1, NO>,
+    events: Vec<Box<dyn EventObject>>,
+}
+impl<C, const NO: usize> Reactor<C, NO>
+where
+    C: Update + Send,
+{
+    /// Creates a new event-driven actor for the client
+    pub fn new(client: Arc<Mutex<C>>) -> Self {
+        Self {
+            actor: Actor::new(client),
+            events: Vec::new(),
+        }
+    }
+    /// Sets the actor name
+    pub fn name<S: Into<String>>(self, name: S) -> Self {
+        Self {
+            actor: self.actor.name(name),
+            ..self
+        }
+    }
+    /// Calls the client methods from the tokio threads dedicated to blocking operations
+    pub fn blocking(self) -> Self {
+        Self {
+            actor: self.actor.blocking(),
+            ..self
+        }
+    }
+    /// Saves the state of the client in the checkpoints of the model
+    #[cfg(feature = "checkpoint")]
+    pub fn checkpoint(self) -> Self
+    where
+        C: gmt_dos_clients::interface::Checkpoint,
+    {
+        Self {
+            actor: self.actor.checkpoint(),
+            ..self
+        }
+    }
+}
+impl<C: Update + Send, const NO: usize> From<C> for Reactor<C, NO> {
+    /// Creates a new event-driven actor for the client
+    fn from(client: C) -> Self {
+        Reactor::new(Arc::new(Mutex::new(client)))
+    }
+}
+impl<C, S, const NO: usize> From<(C, S)> for Reactor<C, NO>
+where
+    C: Update + Send,
+    S: Into<String>,
+{
+    /// Creates a new named event-driven actor for the client
+    fn from((client, name): (C, S)) -> Self {
+        Reactor::new(Arc::new(Mutex::new(client))).name(name)
+    }
+}
+impl<C, const NO: usize> Deref for Reactor<C, NO>
+where
+    C: Update + Send,
+{
+    type Target = Actor<C, 1, NO>;
+    fn deref(&self) -> &Self::Target {
+        &self.actor
+    }
+}
+impl<C, const NO: usize> DerefMut for Reactor<C, NO>
+where
+    C: Update + Send,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.actor
+    }
+}
+impl<C, const NO: usize> Display for Reactor<C, NO>
+where
+    C: Update + Send,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.actor.fmt(f)?;
+        if !self.events.is_empty() {
+            writeln!(f, " - events  #{:>1}:", self.events.len())?;
+            for (k, event) in self.events.iter().enumerate() {
+                writeln!(f, "   {}. {}", 1 + k, event)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<U, C, const NO: usize> InputPort<U> for Reactor<C, NO>
+where
+    Assoc<U>: Send + Sync,
+    U: 'static + Send + Sync + UniqueIdentifier,
+    C: 'static + Update + Send + ReadEvent<U>,
+{
+    fn add_port(&mut self, rx: flume::Receiver<Data<U>>, hash: u64) {
+        self.events.push(Box::new(EventInput::new(
+            rx,
+            Arc::clone(&self.actor.client),
+            hash,
+        )));
+    }
+}
+impl<U, C, const NO: usize> OutputPort<U> for Reactor<C, NO>
+where
+    Assoc<U>: Send + Sync,
+    U: 'static + Send + Sync + UniqueIdentifier,
+    C: 'static + Update + Send + io::Write<U>,
+{
+    fn build_port(&mut self, builder: ActorOutputBuilder) -> (u64, Vec<flume::Receiver<Data<U>>>) {
+        self.actor.build_port(builder)
+    }
+}
+impl<C, const NI: usize, const NO: usize> InputsRate<NI> for Reactor<C, NO> where C: Update + Send {}
+
+impl<C, const NO: usize> Reactor<C, NO>
+where
+    C: 'static + Update + Send,
+{
+    /// Bootstraps the outputs and runs the reactor loop
+    async fn run(&mut self) -> Result<()> {
+        self.actor.set_clocks(1, NO);
+        if self.actor.blocking {
+            self.actor.set_blocking();
+            self.events
+                .iter_mut()
+                .for_each(|event| event.set_blocking());
+        }
+        self.actor.bootstrap(1, NO).await?;
+        crate::print_info(
+            format!("{} loop started", Who::highlight(&self.actor)),
+            None::<&dyn std::error::Error>,
+        );
+        self.react().await
+    }
+    /// Reacts to the data of any of the inputs
+    ///
+    /// The inputs are polled starting after the input of the last event,
+    /// so an input with a steady flow of data cannot hold back the others
+    async fn react(&mut self) -> Result<()> {
+        let mut live: Vec<usize> = (0..self.events.len()).collect();
+        let mut next = 0;
+        let channels: Vec<_> = self
+            .events
+            .iter()
+            .map(|event| {
+                let wait = Wait::Recv {
+                    uid: event.who(),
+                    hash: event.get_hash(),
+                    fill: event.fill(),
+                };
+                self.actor.monitor.register(wait, event.gauge())
+            })
+            .collect();
+        loop {
+            let timer = self.actor.monitor.timer();
+            let (events, monitor) = (&mut self.events, &self.actor.monitor);
+            let mut pending: Vec<_> = events
+                .iter_mut()
+                .enumerate()
+                .filter(|(k, _)| live.contains(k))
+                .collect();
+            let first = pending.iter().position(|(k, _)| *k >= next).unwrap_or(0);
+            pending.rotate_left(first);
+            let waits: Vec<_> = pending.iter().map(|(k, _)| &channels[*k]).collect();
+            waits.iter().for_each(|channel| monitor.wait_on(channel));
+            let ((k, result), ..) = select_all(
+                pending
+                    .into_iter()
+                    .map(|(k, event)| Box::pin(async move { (k, event.wait().await) })),
+            )
+            .await;
+            waits.iter().for_each(|channel| monitor.done(channel));
+            next = k + 1;
+            match result {
+                Ok(_) => {
+                    monitor.received(1);
+                    self.actor.monitor.record(Phase::Inputs, timer);
+                    if self.events[k].read().await {
+                        self.actor.update().await;
+                        if self.actor.outputs.is_some() {
+                            self.actor.distribute().await?;
+                        }
+                    }
+                }
+                // the reactor waits on the remaining inputs
+                Err(e @ ActorError::DropRecv { .. }) => {
+                    live.retain(|&i| i != k);
+                    if live.is_empty() {
+                        return Err(e);
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<C, const NO: usize> Task for Reactor<C, NO>
+where
+    C: 'static + Update + Send,
+{
+    fn spawn(mut self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            self.task().await;
+        })
+    }
+    /// Runs the reactor loop
+    async fn task(&mut self) -> ActorReport {
+        let (name, monitor) = (Who::who(&self.actor), Arc::clone(&self.actor.monitor));
+        ActorReport::catch(name.clone(), &monitor, async {
+            let result = self.run().await;
+            if let Err(e) = result.as_ref() {
+                crate::print_info(
+                    format!("{} loop ended", Who::highlight(&self.actor)),
+                    Some(e),
+                );
+            }
+            let mut report = ActorReport::new(name, monitor.n_step(), result);
+            report.profile = monitor.profile();
+            report.n_dropped = monitor.n_dropped();
+            report
+        })
+        .await
+    }
+    async fn async_run(&mut self) -> Result<()> {
+        self.react().await
+    }
+    /// Checks that the reactor has some events and that the inputs added through its [Actor] are none,
+    /// as the latter are never read
+    fn check_inputs(&self) -> Result<()> {
+        if self.actor.inputs.is_some() {
+            Err(ActorError::NoEventInputs(Who::who(&self.actor)))
+        } else if self.events.is_empty() {
+            Err(ActorError::NoInputs)
+        } else {
+            Ok(())
+        }
+    }
+    fn check_outputs(&self) -> Result<()> {
+        self.actor.check_outputs_rate(NO)
+    }
+    fn n_inputs(&self) -> usize {
+        self.events.len()
+    }
+    fn n_outputs(&self) -> usize {
+        self.actor.n_outputs()
+    }
+    fn inputs_hashes(&self) -> Vec<u64> {
+        self.events.iter().map(|event| event.get_hash()).collect()
+    }
+    fn outputs_hashes(&self) -> Vec<u64> {
+        self.actor.outputs_hashes()
+    }
+    /// Returns the reactor as an actor with a zero inputs rate
+    fn as_plain(&self) -> PlainActor {
+        PlainActor {
+            inputs_rate: 0,
+            inputs: (!self.events.is_empty()).then(|| self.events.iter().map(IO::from).collect()),
+            ..self.actor.as_plain()
+        }
+    }
+    fn set_control(&mut self, control: Control) {
+        self.actor.set_control(control)
+    }
+    fn set_sampling_frequency(&mut self, sampling_frequency: f64) {
+        self.actor.set_sampling_frequency(sampling_frequency)
+    }
+    fn monitor(&self) -> Arc<Monitor> {
+        self.actor.monitor()
+    }
+    fn name(&self) -> String {
+        Who::who(&self.actor)
+    }
+    fn enter_subsystem(&mut self, name: &str) {
+        self.actor.enter_subsystem(name)
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+    #[cfg(feature = "checkpoint")]
+    fn client_state(&self) -> Option<crate::model::ClientState> {
+        self.actor.client_state()
+    }
+    #[cfg(feature = "checkpoint")]
+    fn input_queues(&self) -> Vec<Box<dyn crate::model::InputQueue>> {
+        Vec::new()
+    }
+    #[cfg(feature = "checkpoint")]
+    fn restore(
+        &mut self,
+        snapshot: &crate::model::ActorSnapshot,
+    ) -> std::result::Result<(), crate::model::CheckpointError> {
+        self.actor.restore(snapshot)
+    }
+}
@@ -1,8 +1,11 @@
-use crate::{Actor, ActorError, Result, Update, Who};
+use crate::{
+    model::{ActorReport, Control},
+    Actor, ActorError, InputMode, Result, Update, Who,
+};
 use async_trait::async_trait;
-use std::fmt::Display;
+use std::{any::Any, fmt::Display, sync::Arc};
 
-use super::PlainActor;
+use super::{gcd, Monitor, PlainActor};
 
 #[async_trait]
 pub trait Task: Display + Send {
@@ -28,92 +31,211 @@ pub trait Task: Display + Send {
     */
     fn check_outputs(&self) -> Result<()>;
     /// Run the actor loop
-    async fn task(&mut self);
+    ///
+    /// Returns the report on how the loop ended
+    async fn task(&mut self) -> ActorReport;
     fn n_inputs(&self) -> usize;
     fn n_outputs(&self) -> usize;
     fn inputs_hashes(&self) -> Vec<u64>;
     fn outputs_hashes(&self) -> Vec<u64>;
     fn as_plain(&self) -> PlainActor;
+    /// Returns the actors of the task, as drawn in the model [Graph](crate::model::Graph)
+    fn as_plains(&self) -> Vec<PlainActor> {
+        vec![self.as_plain()]
+    }
+    /// Hands over the control of the actor loop to the model
+    fn set_control(&mut self, control: Control);
+    /// Sets the sampling frequency of the model, giving the simulation time of the outputs data
+    fn set_sampling_frequency(&mut self, sampling_frequency: f64);
+    /// Returns the actor loop state
+    fn monitor(&self) -> Arc<Monitor>;
+    /// Returns the actor name
+    fn name(&self) -> String;
+    /// Nests the actor into the sub-system `name`
+    #[doc(hidden)]
+    fn enter_subsystem(&mut self, name: &str);
+    #[doc(hidden)]
+    fn as_mut_any(&mut self) -> &mut dyn Any;
+    /// Returns the serializer of the client state, if the client is checkpointed
+    #[cfg(feature = "checkpoint")]
+    fn client_state(&self) -> Option<crate::model::ClientState>;
+    /// Returns the data queues of the inputs saved at the checkpoints
+    #[cfg(feature = "checkpoint")]
+    fn input_queues(&self) -> Vec<Box<dyn crate::model::InputQueue>>;
+    /// Restores the actor from a snapshot of a checkpointed model
+    #[cfg(feature = "checkpoint")]
+    fn restore(
+        &mut self,
+        snapshot: &crate::model::ActorSnapshot,
+    ) -> std::result::Result<(), crate::model::CheckpointError>;
 }
 
-#[async_trait]
-impl<C, const NI: usize, const NO: usize> Task for Actor<C, NI, NO>
+impl<C, const NI: usize, const NO: usize> Actor<C, NI, NO>
 where
     C: 'static + Update + Send,
 {
-    /// Run the actor loop in a dedicated thread
-    fn spawn(mut self) -> tokio::task::JoinHandle<()> {
-        tokio::spawn(async move {
-            self.task().await;
-        })
+    /// Runs the actor loop with the given inputs and outputs rates
+    ///
+    /// A panic of the client ends the loop and is recorded in the actor report
+    pub(super) async fn run(&mut self, ni: usize, no: usize) -> ActorReport {
+        let (name, monitor) = (Who::who(self), Arc::clone(&self.monitor));
+        ActorReport::catch(name, &monitor, self.bootstrap_and_loop(ni, no)).await
     }
-    /// Run the actor loop
-    async fn task(&mut self) {
-        match self.bootstrap().await {
-            Err(e) => crate::print_info(
-                format!("{} bootstrapping failed", Who::highlight(self)),
-                Some(&e),
-            ),
+    /// Bootstraps the outputs and runs the actor loop
+    async fn bootstrap_and_loop(&mut self, ni: usize, no: usize) -> ActorReport {
+        self.set_clocks(ni, no);
+        if self.blocking {
+            self.set_blocking();
+        }
+        let result = match self.bootstrap(ni, no).await {
+            Err(e) => {
+                crate::print_info(
+                    format!("{} bootstrapping failed", Who::highlight(self)),
+                    Some(&e),
+                );
+                Err(e)
+            }
             Ok(_) => {
                 crate::print_info(
                     format!("{} loop started", Who::highlight(self)),
                     None::<&dyn std::error::Error>,
                 );
-                if let Err(e) = self.async_run().await {
-                    crate::print_info(format!("{} loop ended", Who::highlight(self)), Some(&e));
+                let result = self.run_loop(ni, no).await;
+                if let Err(e) = result.as_ref() {
+                    crate::print_info(format!("{} loop ended", Who::highlight(self)), Some(e));
                 }
+                result
             }
-        }
+        };
+        let mut report = ActorReport::new(Who::who(self), self.monitor.n_step(), result);
+        report.profile = self.monitor.profile();
+        report.n_dropped = self.monitor.n_dropped();
+        report
     }
-
-    /// Starts the actor infinite loop
-    async fn async_run(&mut self) -> Result<()> {
+    /// Starts the actor infinite loop with the given inputs and outputs rates
+    ///
+    /// The loop starts at the cycle given by the number of client updates,
+    /// which is only non-zero for an actor resumed from a checkpoint
+    pub(super) async fn run_loop(&mut self, ni: usize, no: usize) -> Result<()> {
+        let n_step = self.monitor.n_step();
         match (self.inputs.as_ref(), self.outputs.as_ref()) {
             (Some(_), Some(_)) => {
-                if NO >= NI {
+                if no.is_multiple_of(ni) {
                     // Decimation
+                    let mut start = n_step % (no / ni);
                     loop {
-                        for _ in 0..NO / NI {
-                            self.collect().await?.client.lock().await.update();
+                        self.proceed(ni).await?;
+                        for _ in start..no / ni {
+                            self.collect().await?.update().await;
                         }
+                        start = 0;
                         self.distribute().await?;
                     }
-                } else {
+                } else if ni.is_multiple_of(no) {
                     // Upsampling
                     loop {
-                        self.collect().await?.client.lock().await.update();
-                        for _ in 0..NI / NO {
+                        self.proceed(ni).await?;
+                        self.collect().await?.update().await;
+                        for _ in 0..ni / no {
                             self.distribute().await?;
                         }
                     }
+                } else {
+                    // Rational rate transition:
+                    // over a period of lcm(ni,no) time steps, the inputs are collected
+                    // every ni time steps and the outputs are distributed every no time steps
+                    let period = ni / gcd(ni, no) * no;
+                    let mut start = (n_step % (period / ni)) * ni;
+                    loop {
+                        self.proceed(ni).await?;
+                        for step in start..period {
+                            if step % ni == 0 {
+                                self.collect().await?.update().await;
+                            }
+                            if (step + 1) % no == 0 {
+                                self.distribute().await?;
+                            }
+                        }
+                        start = 0;
+                    }
                 }
             }
             (None, Some(_)) => loop {
                 // Initiator
-                self.client.lock().await.update();
+                self.proceed(no).await?;
+                self.update().await;
                 self.distribute().await?;
             },
             (Some(_), None) => loop {
                 // Terminator
-                self.collect().await?.client.lock().await.update();
+                self.proceed(ni).await?;
+                self.collect().await?.update().await;
             },
             (None, None) => Ok(()),
         }
     }
-    fn check_inputs(&self) -> Result<()> {
+    /// Waits for the model control to let the actor start a new cycle
+    ///
+    /// The rate converts the number of client updates into simulation time steps
+    async fn proceed(&mut self, rate: usize) -> Result<()> {
+        match self.control.as_mut() {
+            Some(control) => control.proceed(&self.monitor, rate).await,
+            None => Ok(()),
+        }
+    }
+    /// Validates the inputs against the given inputs rate
+    ///
+    /// At least one input must wait for its data, otherwise the actor loop would never wait,
+    /// and the linked inputs are only fed within a fused block
+    pub(super) fn check_inputs_rate(&self, ni: usize) -> Result<()> {
+        if !self.fused && !self.linked.is_empty() {
+            return Err(ActorError::Unfused(Who::who(self)));
+        }
         match self.inputs {
-            Some(_) if NI == 0 => Err(ActorError::SomeInputsZeroRate(Who::who(self))),
-            None if NI > 0 => Err(ActorError::NoInputsPositiveRate(Who::who(self))),
+            Some(_) if ni == 0 => Err(ActorError::SomeInputsZeroRate(Who::who(self))),
+            None if ni > 0 => Err(ActorError::NoInputsPositiveRate(Who::who(self))),
+            Some(ref inputs) if inputs.iter().all(|input| input.mode() != InputMode::Wait) => {
+                Err(ActorError::NoWaitingInput(Who::who(self)))
+            }
             _ => Ok(()),
         }
     }
-    fn check_outputs(&self) -> Result<()> {
+    /// Validates the outputs against the given outputs rate
+    pub(super) fn check_outputs_rate(&self, no: usize) -> Result<()> {
         match self.outputs {
-            Some(_) if NO == 0 => Err(ActorError::SomeOutputsZeroRate(Who::who(self))),
-            None if NO > 0 => Err(ActorError::NoOutputsPositiveRate(Who::who(self))),
+            Some(_) if no == 0 => Err(ActorError::SomeOutputsZeroRate(Who::who(self))),
+            None if no > 0 => Err(ActorError::NoOutputsPositiveRate(Who::who(self))),
             _ => Ok(()),
         }
     }
+}
+
+#[async_trait]
+impl<C, const NI: usize, const NO: usize> Task for Actor<C, NI, NO>
+where
+    C: 'static + Update + Send,
+{
+    /// Run the actor loop in a dedicated thread
+    fn spawn(mut self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            self.task().await;
+        })
+    }
+    /// Run the actor loop
+    async fn task(&mut self) -> ActorReport {
+        self.run(NI, NO).await
+    }
+
+    /// Starts the actor infinite loop
+    async fn async_run(&mut self) -> Result<()> {
+        self.run_loop(NI, NO).await
+    }
+    fn check_inputs(&self) -> Result<()> {
+        self.check_inputs_rate(NI)
+    }
+    fn check_outputs(&self) -> Result<()> {
+        self.check_outputs_rate(NO)
+    }
     fn n_inputs(&self) -> usize {
         self.inputs.as_ref().map_or(0, |i| i.len())
     }
@@ -138,4 +260,37 @@ where
     fn as_plain(&self) -> PlainActor {
         self.into()
     }
+    fn set_control(&mut self, control: Control) {
+        self.control = Some(control);
+    }
+    fn set_sampling_frequency(&mut self, sampling_frequency: f64) {
+        self.sampling_frequency = Some(sampling_frequency);
+    }
+    fn monitor(&self) -> Arc<Monitor> {
+        Arc::clone(&self.monitor)
+    }
+    fn name(&self) -> String {
+        Who::who(self)
+    }
+    fn enter_subsystem(&mut self, name: &str) {
+        self.subsystem.insert(0, name.to_string());
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+    #[cfg(feature = "checkpoint")]
+    fn client_state(&self) -> Option<crate::model::ClientState> {
+        Actor::client_state(self)
+    }
+    #[cfg(feature = "checkpoint")]
+    fn input_queues(&self) -> Vec<Box<dyn crate::model::InputQueue>> {
+        Actor::input_queues(self)
+    }
+    #[cfg(feature = "checkpoint")]
+    fn restore(
+        &mut self,
+        snapshot: &crate::model::ActorSnapshot,
+    ) -> std::result::Result<(), crate::model::CheckpointError> {
+        Actor::restore(self, snapshot)
+    }
 }
@@ -15,7 +15,9 @@ and [Write] traits.
 */
 
 mod input;
-pub(crate) use input::{Input, InputObject};
+pub(crate) use input::{Input, InputObject, LinkedInput};
+mod event;
+pub(crate) use event::{EventInput, EventObject};
 mod output;
-pub(crate) use output::{Output, OutputObject};
+pub(crate) use output::{Clock, Output, OutputObject, Policy};
 pub type S<U> = crate::interface::Data<U>;
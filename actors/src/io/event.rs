@@ -0,0 +1,158 @@
+use super::S;
+use crate::interface::{ReadEvent, Stamp};
+use crate::{
+    actor::{offload, Fill, Gauge},
+    ActorError, Result, UniqueIdentifier, Who,
+};
+use async_trait::async_trait;
+use flume::Receiver;
+use std::any::type_name;
+use std::marker::PhantomData;
+use std::{fmt::Display, sync::Arc};
+use tokio::sync::Mutex;
+
+/// [Reactor](crate::actor::Reactor)s input
+///
+/// The data is received first and kept until it is read into the client,
+/// so an input can stop waiting for its data without losing it.
+pub(crate) struct EventInput<C, U>
+where
+    U: UniqueIdentifier,
+    C: ReadEvent<U>,
+{
+    rx: Receiver<S<U>>,
+    client: Arc<Mutex<C>>,
+    hash: u64,
+    data: Option<S<U>>,
+    stamp: Option<Stamp>,
+    blocking: bool,
+    uid: PhantomData<U>,
+}
+impl<C, U> EventInput<C, U>
+where
+    U: UniqueIdentifier,
+    C: ReadEvent<U>,
+{
+    /// Creates a new event input from a [Receiver], a [Reactor](crate::actor::Reactor) client and an identifier [hash]
+    pub fn new(rx: Receiver<S<U>>, client: Arc<Mutex<C>>, hash: u64) -> Self {
+        Self {
+            rx,
+            client,
+            hash,
+            data: None,
+            stamp: None,
+            blocking: false,
+            uid: PhantomData,
+        }
+    }
+}
+impl<C, U> Who<U> for EventInput<C, U>
+where
+    C: ReadEvent<U>,
+    U: UniqueIdentifier,
+{
+}
+impl<C, U> Display for EventInput<C, U>
+where
+    C: ReadEvent<U>,
+    U: UniqueIdentifier,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:>19}: {} (event)", self.hash, Who::who(self))
+    }
+}
+
+#[async_trait]
+pub(crate) trait EventObject: Display + Send + Sync {
+    /// Waits for the data of the next event
+    ///
+    /// The data is kept until it is read, so the waiting can be cancelled at any time
+    async fn wait(&mut self) -> Result<()>;
+    /// Reads the data of the event into the client
+    ///
+    /// Returns `true` if the client reacts to the event
+    async fn read(&mut self) -> bool;
+    /// Returns the input UID
+    fn who(&self) -> String;
+    /// Gets the input hash
+    fn get_hash(&self) -> u64;
+    fn capacity(&self) -> Option<usize>;
+    /// Returns the fill level of the input channel
+    fn fill(&self) -> Fill;
+    /// Returns the gauge of the fill level of the input channel
+    fn gauge(&self) -> Gauge;
+    /// Reads the data into the client from the threads dedicated to blocking operations
+    fn set_blocking(&mut self);
+}
+
+#[async_trait]
+impl<C, U> EventObject for EventInput<C, U>
+where
+    C: 'static + ReadEvent<U> + Send,
+    U: 'static + Send + Sync + UniqueIdentifier,
+    S<U>: Send + Sync,
+{
+    async fn wait(&mut self) -> Result<()> {
+        if self.data.is_some() {
+            return Ok(());
+        }
+        log::debug!("{} waiting", Who::highlight(self));
+        let data = self
+            .rx
+            .recv_async()
+            .await
+            .map_err(|e| ActorError::DropRecv {
+                msg: Who::who(self),
+                source: e,
+            })?;
+        // the data of a producer must come in order of simulation steps
+        if let (Some(stamp), Some(previous)) = (data.stamp(), self.stamp.as_ref()) {
+            if stamp.step <= previous.step {
+                return Err(ActorError::Unordered {
+                    uid: Who::who(self),
+                    step: stamp.step,
+                    previous: previous.step,
+                });
+            }
+        }
+        self.stamp = data.stamp().copied();
+        self.data = Some(data);
+        Ok(())
+    }
+    async fn read(&mut self) -> bool {
+        let Some(data) = self.data.take() else {
+            return false;
+        };
+        let fired = if self.blocking {
+            let client = Arc::clone(&self.client);
+            offload(move || client.blocking_lock().read_event(data)).await
+        } else {
+            (*self.client.lock().await).read_event(data)
+        };
+        log::debug!(
+            "{} read ({}, fired: {fired})",
+            Who::highlight(self),
+            type_name::<C>()
+        );
+        fired
+    }
+    fn who(&self) -> String {
+        Who::who(self)
+    }
+    fn get_hash(&self) -> u64 {
+        self.hash
+    }
+    fn capacity(&self) -> Option<usize> {
+        self.rx.capacity()
+    }
+    fn fill(&self) -> Fill {
+        Fill(self.rx.len(), self.rx.capacity())
+    }
+    fn gauge(&self) -> Gauge {
+        let rx = self.rx.clone();
+        Gauge::new(move || vec![Fill(rx.len(), rx.capacity())])
+    }
+    fn set_blocking(&mut self) {
+        self.blocking = true;
+    }
+}
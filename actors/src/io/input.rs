@@ -1,12 +1,25 @@
 use super::S;
-use crate::interface::Read;
-use crate::{ActorError, Result, UniqueIdentifier, Who};
+use crate::interface::{Read, Stamp};
+use crate::{
+    actor::{offload, Fill, Gauge},
+    ActorError, InputMode, Result, UniqueIdentifier, Who,
+};
 use async_trait::async_trait;
-use flume::Receiver;
-use std::any::type_name;
+use flume::{Receiver, TryRecvError};
+use std::any::{type_name, Any};
 use std::fmt::Debug;
+use std::marker::PhantomData;
 use std::{fmt::Display, sync::Arc};
 use tokio::sync::Mutex;
+#[cfg(feature = "checkpoint")]
+use {
+    crate::{
+        actor::Codec,
+        interface::Assoc,
+        model::{CheckpointError, InputQueue},
+    },
+    std::collections::VecDeque,
+};
 
 /// [Actor](crate::Actor)s input
 pub(crate) struct Input<C, T, U, const N: usize>
@@ -17,6 +30,14 @@ where
     rx: Receiver<S<U>>,
     client: Arc<Mutex<C>>,
     hash: u64,
+    stamp: Option<Stamp>,
+    blocking: bool,
+    mode: InputMode,
+    /// Data taken from the channel at a checkpoint, read before the data of the channel
+    #[cfg(feature = "checkpoint")]
+    queue: Arc<std::sync::Mutex<Pending<U>>>,
+    #[cfg(feature = "checkpoint")]
+    codec: Option<Codec<U>>,
 }
 impl<C, T, U, const N: usize> Input<C, T, U, N>
 where
@@ -25,7 +46,74 @@ where
 {
     /// Creates a new intput from a [Receiver], an [Actor] client and an identifier [hash]
     pub fn new(rx: Receiver<S<U>>, client: Arc<Mutex<C>>, hash: u64) -> Self {
-        Self { rx, client, hash }
+        Self {
+            rx,
+            client,
+            hash,
+            stamp: None,
+            blocking: false,
+            mode: InputMode::Wait,
+            #[cfg(feature = "checkpoint")]
+            queue: Arc::new(std::sync::Mutex::new(Pending {
+                data: VecDeque::new(),
+                receiving: false,
+            })),
+            #[cfg(feature = "checkpoint")]
+            codec: None,
+        }
+    }
+    /// Sets the input mode
+    pub fn set_mode(&mut self, mode: InputMode) {
+        self.mode = mode;
+    }
+    /// Sets the serializer of the data queued at a checkpoint
+    #[cfg(feature = "checkpoint")]
+    pub fn set_codec(&mut self, codec: Codec<U>) {
+        self.codec = Some(codec);
+    }
+    /// Receives the data according to the input mode
+    ///
+    /// Returns the number of data taken from the channel and the data to read, if any
+    async fn take(&self) -> Result<(usize, Option<S<U>>)> {
+        // the data queued at a checkpoint has already been taken from the channel
+        // and the channel is not drained at a checkpoint while the input waits on it
+        #[cfg(feature = "checkpoint")]
+        let _receiving = {
+            let mut queue = self.queue.lock().unwrap();
+            if !queue.data.is_empty() {
+                return Ok(match self.mode {
+                    InputMode::Latest => {
+                        let latest = queue.data.pop_back();
+                        queue.data.clear();
+                        self.rx
+                            .try_iter()
+                            .fold((0, latest), |(n, _), data| (n + 1, Some(data)))
+                    }
+                    InputMode::Wait | InputMode::Optional => (0, queue.data.pop_front()),
+                });
+            }
+            (self.mode == InputMode::Wait).then(|| Receiving::new(&self.queue, &mut queue))
+        };
+        let drop_recv = |e| ActorError::DropRecv {
+            msg: Who::who(self),
+            source: e,
+        };
+        match self.mode {
+            InputMode::Wait => {
+                let data = self.rx.recv_async().await.map_err(drop_recv)?;
+                Ok((1, Some(data)))
+            }
+            // the client keeps the last value when nothing has arrived
+            // or when the sending actor has exited
+            InputMode::Optional => match self.rx.try_recv() {
+                Ok(data) => Ok((1, Some(data))),
+                Err(TryRecvError::Empty | TryRecvError::Disconnected) => Ok((0, None)),
+            },
+            InputMode::Latest => Ok(self
+                .rx
+                .try_iter()
+                .fold((0, None), |(n, _), data| (n + 1, Some(data)))),
+        }
     }
 }
 impl<C, T, U, const N: usize> Who<U> for Input<C, T, U, N>
@@ -54,6 +142,9 @@ where
             .field("rx", &self.rx)
             .field("client", &self.client)
             .field("hash", &self.hash)
+            .field("stamp", &self.stamp)
+            .field("blocking", &self.blocking)
+            .field("mode", &self.mode)
             .finish()
     }
 }
@@ -61,12 +152,31 @@ where
 #[async_trait]
 pub(crate) trait InputObject: Display + Send + Sync {
     /// Receives output data
-    async fn recv(&mut self) -> Result<()>;
+    ///
+    /// Returns the number of data taken from the channel
+    async fn recv(&mut self) -> Result<usize>;
     /// Returns the input UID
     fn who(&self) -> String;
     /// Gets the input hash
     fn get_hash(&self) -> u64;
     fn capacity(&self) -> Option<usize>;
+    /// Returns the fill level of the input channel
+    fn fill(&self) -> Fill;
+    /// Returns the gauge of the fill level of the input channel
+    fn gauge(&self) -> Gauge;
+    /// Returns the stamp of the last received data
+    fn stamp(&self) -> Option<Stamp>;
+    /// Reads the data into the client from the threads dedicated to blocking operations
+    fn set_blocking(&mut self);
+    /// Returns the input mode
+    fn mode(&self) -> InputMode;
+    /// Returns the data queue saved at the checkpoints, if the input has a channel
+    #[cfg(feature = "checkpoint")]
+    fn queue(&self) -> Option<Box<dyn InputQueue>>;
+    /// Queues the serialized data of a checkpoint, to be read before the data of the channel
+    #[cfg(feature = "checkpoint")]
+    fn restore_queue(&mut self, data: &[Vec<u8>]) -> std::result::Result<(), CheckpointError>;
+    fn as_mut_any(&mut self) -> &mut dyn Any;
 }
 
 impl Debug for Box<dyn InputObject> {
@@ -78,26 +188,36 @@ impl Debug for Box<dyn InputObject> {
 #[async_trait]
 impl<C, T, U, const N: usize> InputObject for Input<C, T, U, N>
 where
-    C: Read<U> + Send,
-    T: Send + Sync,
-    U: Send + Sync + UniqueIdentifier<DataType = T>,
+    C: 'static + Read<U> + Send,
+    T: 'static + Send + Sync,
+    U: 'static + Send + Sync + UniqueIdentifier<DataType = T>,
 {
-    async fn recv(&mut self) -> Result<()> {
+    async fn recv(&mut self) -> Result<usize> {
         log::debug!("{} receiving", Who::highlight(self));
-        // log::debug!("{} receiving (locking client)", Who::who(self));
-        let mut client = self.client.lock().await;
-        // log::debug!("{} receiving (client locked)", Who::who(self));
-        (*client).read(
-            self.rx
-                .recv_async()
-                .await
-                .map_err(|e| ActorError::DropRecv {
-                    msg: Who::lite(self),
-                    source: e,
-                })?,
-        );
+        let (n, Some(data)) = self.take().await? else {
+            return Ok(0);
+        };
+        // the data of a producer must come in order of simulation steps
+        if let (Some(stamp), Some(previous)) = (data.stamp(), self.stamp.as_ref()) {
+            if stamp.step <= previous.step {
+                return Err(ActorError::Unordered {
+                    uid: Who::who(self),
+                    step: stamp.step,
+                    previous: previous.step,
+                });
+            }
+        }
+        self.stamp = data.stamp().copied();
+        // the client is locked only once the data is available,
+        // so it can be accessed while the actor is waiting for its inputs
+        if self.blocking {
+            let client = Arc::clone(&self.client);
+            offload(move || client.blocking_lock().read(data)).await;
+        } else {
+            (*self.client.lock().await).read(data);
+        }
         log::debug!("{} received ({})", Who::highlight(self), type_name::<C>());
-        Ok(())
+        Ok(n)
     }
     fn who(&self) -> String {
         Who::who(self)
@@ -108,4 +228,181 @@ where
     fn capacity(&self) -> Option<usize> {
         self.rx.capacity()
     }
+    fn fill(&self) -> Fill {
+        Fill(self.rx.len(), self.rx.capacity())
+    }
+    fn gauge(&self) -> Gauge {
+        let rx = self.rx.clone();
+        Gauge::new(move || vec![Fill(rx.len(), rx.capacity())])
+    }
+    fn stamp(&self) -> Option<Stamp> {
+        self.stamp
+    }
+    fn set_blocking(&mut self) {
+        self.blocking = true;
+    }
+    fn mode(&self) -> InputMode {
+        self.mode
+    }
+    #[cfg(feature = "checkpoint")]
+    fn queue(&self) -> Option<Box<dyn InputQueue>> {
+        Some(Box::new(Queue {
+            uid: Who::who(self),
+            hash: self.hash,
+            rx: self.rx.clone(),
+            queue: Arc::clone(&self.queue),
+            codec: self.codec,
+        }))
+    }
+    #[cfg(feature = "checkpoint")]
+    fn restore_queue(&mut self, data: &[Vec<u8>]) -> std::result::Result<(), CheckpointError> {
+        let codec = self
+            .codec
+            .ok_or_else(|| CheckpointError::Unsaved(Who::who(self)))?;
+        let mut queue = self.queue.lock().unwrap();
+        for bytes in data {
+            queue.data.push_back((codec.restore)(bytes)?);
+        }
+        Ok(())
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Data of an [Input] taken from the channel at a checkpoint
+#[cfg(feature = "checkpoint")]
+struct Pending<U: UniqueIdentifier> {
+    data: VecDeque<S<U>>,
+    /// Set while the input waits on the channel
+    receiving: bool,
+}
+/// Flags an [Input] as waiting on its channel until it is dropped
+#[cfg(feature = "checkpoint")]
+struct Receiving<U: UniqueIdentifier>(Arc<std::sync::Mutex<Pending<U>>>);
+#[cfg(feature = "checkpoint")]
+impl<U: UniqueIdentifier> Receiving<U> {
+    fn new(queue: &Arc<std::sync::Mutex<Pending<U>>>, pending: &mut Pending<U>) -> Self {
+        pending.receiving = true;
+        Self(Arc::clone(queue))
+    }
+}
+#[cfg(feature = "checkpoint")]
+impl<U: UniqueIdentifier> Drop for Receiving<U> {
+    fn drop(&mut self) {
+        self.0.lock().unwrap().receiving = false;
+    }
+}
+
+/// Data queue of an [Input] at a checkpoint
+#[cfg(feature = "checkpoint")]
+struct Queue<U: UniqueIdentifier> {
+    uid: String,
+    hash: u64,
+    rx: Receiver<S<U>>,
+    queue: Arc<std::sync::Mutex<Pending<U>>>,
+    codec: Option<Codec<U>>,
+}
+#[cfg(feature = "checkpoint")]
+impl<U> InputQueue for Queue<U>
+where
+    U: UniqueIdentifier,
+    Assoc<U>: Send + Sync,
+{
+    fn hash(&self) -> u64 {
+        self.hash
+    }
+    fn is_receiving(&self) -> bool {
+        self.queue.lock().unwrap().receiving
+    }
+    fn drain(&self) -> usize {
+        let mut queue = self.queue.lock().unwrap();
+        if queue.receiving {
+            return 0;
+        }
+        let n = queue.data.len();
+        queue.data.extend(self.rx.try_iter());
+        queue.data.len() - n
+    }
+    fn save(&self) -> std::result::Result<Vec<Vec<u8>>, CheckpointError> {
+        let queue = self.queue.lock().unwrap();
+        if queue.data.is_empty() {
+            return Ok(vec![]);
+        }
+        let codec = self
+            .codec
+            .ok_or_else(|| CheckpointError::Unsaved(self.uid.clone()))?;
+        Ok(queue
+            .data
+            .iter()
+            .map(|data| (codec.save)(data))
+            .collect::<bincode::Result<Vec<_>>>()?)
+    }
+}
+
+/// [Actor](crate::Actor)s input fed directly by the output of another actor
+///
+/// The data is read into the client by the output itself,
+/// both actors being stepped one after the other within a [Fused](crate::actor::Fused) block
+pub(crate) struct LinkedInput<U: UniqueIdentifier> {
+    hash: u64,
+    uid: PhantomData<U>,
+}
+impl<U: UniqueIdentifier> LinkedInput<U> {
+    /// Creates a new linked input with the hash of the output
+    pub fn new(hash: u64) -> Self {
+        Self {
+            hash,
+            uid: PhantomData,
+        }
+    }
+}
+impl<U: UniqueIdentifier> Who<U> for LinkedInput<U> {}
+impl<U: UniqueIdentifier> Display for LinkedInput<U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:>19}: {} (linked)", self.hash, Who::who(self))
+    }
+}
+
+#[async_trait]
+impl<U> InputObject for LinkedInput<U>
+where
+    U: 'static + Send + Sync + UniqueIdentifier,
+{
+    async fn recv(&mut self) -> Result<usize> {
+        Ok(1)
+    }
+    fn who(&self) -> String {
+        Who::who(self)
+    }
+    fn get_hash(&self) -> u64 {
+        self.hash
+    }
+    fn capacity(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn fill(&self) -> Fill {
+        Fill(0, Some(1))
+    }
+    fn gauge(&self) -> Gauge {
+        Gauge::new(|| vec![Fill(0, Some(1))])
+    }
+    fn stamp(&self) -> Option<Stamp> {
+        None
+    }
+    fn set_blocking(&mut self) {}
+    fn mode(&self) -> InputMode {
+        InputMode::Wait
+    }
+    #[cfg(feature = "checkpoint")]
+    fn queue(&self) -> Option<Box<dyn InputQueue>> {
+        None
+    }
+    #[cfg(feature = "checkpoint")]
+    fn restore_queue(&mut self, _data: &[Vec<u8>]) -> std::result::Result<(), CheckpointError> {
+        Err(CheckpointError::Unsaved(Who::who(self)))
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
 }
@@ -1,20 +1,68 @@
 use super::S;
-use crate::interface::{Assoc, Write};
-use crate::{ActorError, Result, UniqueIdentifier, Who};
+use crate::interface::{Assoc, Read, Stamp, Write};
+use crate::{
+    actor::{offload, Fill, Gauge, ProbeObject},
+    ActorError, Result, UniqueIdentifier, Who,
+};
 use async_trait::async_trait;
-use flume::Sender;
+use flume::{Receiver, SendError, Sender, TrySendError};
 use futures::future::join_all;
 use std::any::{type_name, Any};
 use std::fmt::Debug;
 use std::{fmt::Display, sync::Arc};
 use tokio::sync::Mutex;
 
+/// Behavior of an output when a channel is full
+pub(crate) enum Policy<U: UniqueIdentifier> {
+    /// Waits for room in the channel
+    Block,
+    /// Drops the new data
+    DropNewest,
+    /// Drops the oldest data with the receiving end of the channel
+    DropOldest(Receiver<S<U>>),
+}
+impl<U: UniqueIdentifier> Debug for Policy<U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Policy::Block => write!(f, "Block"),
+            Policy::DropNewest => write!(f, "DropNewest"),
+            Policy::DropOldest(_) => write!(f, "DropOldest"),
+        }
+    }
+}
+
+/// Time stamping of the data sent by an output
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Clock {
+    /// Identifier of the actor
+    pub producer: u64,
+    /// Number of simulation steps between 2 consecutive data
+    pub rate: usize,
+    /// Sampling frequency of the model in Hz
+    pub sampling_frequency: Option<f64>,
+}
+impl Clock {
+    /// Returns the stamp of the `n`th data sent by the output
+    fn stamp(&self, n: usize) -> Stamp {
+        let step = n * self.rate;
+        Stamp {
+            step,
+            time: self.sampling_frequency.map(|hz| step as f64 / hz),
+            producer: self.producer,
+        }
+    }
+}
+
+/// Client reading directly the data of an output
+pub(crate) type Reader<U> = Arc<Mutex<dyn Read<U> + Send>>;
+
 pub(crate) struct OutputBuilder<C, T, U, const N: usize>
 where
     U: UniqueIdentifier<DataType = T>,
     C: Write<U>,
 {
     tx: Vec<Sender<S<U>>>,
+    policies: Vec<Policy<U>>,
     client: Arc<Mutex<C>>,
     bootstrap: bool,
 }
@@ -26,12 +74,17 @@ where
     pub fn new(client: Arc<Mutex<C>>) -> Self {
         Self {
             tx: Vec::new(),
+            policies: Vec::new(),
             client,
             bootstrap: false,
         }
     }
-    pub fn senders(self, tx: Vec<Sender<S<U>>>) -> Self {
-        Self { tx, ..self }
+    pub fn senders(self, tx: Vec<Sender<S<U>>>, policies: Vec<Policy<U>>) -> Self {
+        Self {
+            tx,
+            policies,
+            ..self
+        }
     }
     pub fn bootstrap(self, bootstrap: bool) -> Self {
         Self { bootstrap, ..self }
@@ -40,9 +93,20 @@ where
         Output {
             data: None,
             tx: self.tx,
+            policies: self.policies,
             client: self.client,
             bootstrap: self.bootstrap,
             hash: 0,
+            dropped: 0,
+            clock: Clock {
+                producer: 0,
+                rate: N,
+                sampling_frequency: None,
+            },
+            n_sent: 0,
+            blocking: false,
+            readers: Vec::new(),
+            probes: Vec::new(),
         }
     }
 }
@@ -55,9 +119,16 @@ where
 {
     data: Option<S<U>>,
     tx: Vec<Sender<S<U>>>,
+    policies: Vec<Policy<U>>,
     client: Arc<Mutex<C>>,
     bootstrap: bool,
     hash: u64,
+    dropped: usize,
+    clock: Clock,
+    n_sent: usize,
+    blocking: bool,
+    readers: Vec<Reader<U>>,
+    probes: Vec<Box<dyn ProbeObject<U>>>,
 }
 impl<C, T, U, const N: usize> Output<C, T, U, N>
 where
@@ -68,8 +139,23 @@ where
     pub fn builder(client: Arc<Mutex<C>>) -> OutputBuilder<C, T, U, N> {
         OutputBuilder::new(client)
     }
-    pub fn tx_push(&mut self, mut tx: Vec<Sender<S<U>>>) -> &mut Self {
+    pub fn tx_push(
+        &mut self,
+        mut tx: Vec<Sender<S<U>>>,
+        mut policies: Vec<Policy<U>>,
+    ) -> &mut Self {
         self.tx.append(&mut tx);
+        self.policies.append(&mut policies);
+        self
+    }
+    /// Adds a client reading the output data without a channel
+    pub fn reader_push(&mut self, reader: Reader<U>) -> &mut Self {
+        self.readers.push(reader);
+        self
+    }
+    /// Adds a probe recording the data sent by the output
+    pub fn probe_push(&mut self, probe: Box<dyn ProbeObject<U>>) -> &mut Self {
+        self.probes.push(probe);
         self
     }
 }
@@ -103,9 +189,15 @@ impl<C: Write<U> + Debug, T: Debug, U: UniqueIdentifier<DataType = T>, const N:
         f.debug_struct("Output")
             .field("data", &self.data)
             .field("tx", &self.tx)
+            .field("policies", &self.policies)
             .field("client", &self.client)
             .field("bootstrap", &self.bootstrap)
             .field("hash", &self.hash)
+            .field("clock", &self.clock)
+            .field("n_sent", &self.n_sent)
+            .field("blocking", &self.blocking)
+            .field("readers", &self.readers.len())
+            .field("probes", &self.probes.len())
             .finish()
     }
 }
@@ -119,6 +211,16 @@ pub(crate) trait OutputObject: Any + Display + Send + Sync {
     fn highlight(&self) -> String;
     fn set_hash(&mut self, hash: u64);
     fn get_hash(&self) -> u64;
+    /// Returns the fill level of the channel of each receiver
+    fn fill(&self) -> Vec<Fill>;
+    /// Returns the gauge of the fill level of the channel of each receiver
+    fn gauge(&self) -> Gauge;
+    /// Returns the number of data dropped by the overflow policies
+    fn dropped(&self) -> usize;
+    /// Sets the time stamping of the data, `n_sent` data having already been sent
+    fn set_clock(&mut self, clock: Clock, n_sent: usize);
+    /// Writes the client data from the threads dedicated to blocking operations
+    fn set_blocking(&mut self);
     fn as_any(&self) -> &dyn Any;
     fn as_mut_any(&mut self) -> &mut dyn Any;
 }
@@ -139,29 +241,85 @@ where
 {
     /// Sends output data
     async fn send(&mut self) -> Result<()> {
-        self.data = (*self.client.lock().await).write();
+        let stamp = self.clock.stamp(self.n_sent);
+        let data = if self.blocking {
+            let client = Arc::clone(&self.client);
+            offload(move || client.blocking_lock().write()).await
+        } else {
+            (*self.client.lock().await).write()
+        };
+        self.data = data.map(|data| data.with_stamp(stamp));
         if let Some(data) = &self.data {
+            self.n_sent += 1;
             log::debug!("{} sending", Who::highlight(self));
             let futures: Vec<_> = self
                 .tx
                 .iter()
-                .map(|tx| tx.send_async(data.clone()))
+                .zip(&self.policies)
+                .map(|(tx, policy)| async move {
+                    match policy {
+                        Policy::Block => tx.send_async(data.clone()).await.map(|_| 0),
+                        Policy::DropNewest => match tx.try_send(data.clone()) {
+                            Ok(_) => Ok(0),
+                            Err(TrySendError::Full(_)) => Ok(1),
+                            Err(TrySendError::Disconnected(data)) => Err(SendError(data)),
+                        },
+                        Policy::DropOldest(rx) => {
+                            // the receiver of the policy is the only one left once the input is dropped
+                            if tx.receiver_count() == 1 {
+                                return Err(SendError(data.clone()));
+                            }
+                            let (mut data, mut dropped) = (data.clone(), 0);
+                            loop {
+                                match tx.try_send(data) {
+                                    Ok(_) => break Ok(dropped),
+                                    Err(TrySendError::Full(full)) => {
+                                        if rx.try_recv().is_ok() {
+                                            dropped += 1;
+                                        }
+                                        data = full;
+                                    }
+                                    Err(TrySendError::Disconnected(data)) => {
+                                        break Err(SendError(data))
+                                    }
+                                }
+                            }
+                        }
+                    }
+                })
                 .collect();
-            join_all(futures)
+            let dropped: usize = join_all(futures)
                 .await
                 .into_iter()
-                .collect::<std::result::Result<Vec<()>, flume::SendError<_>>>()
+                .collect::<std::result::Result<Vec<usize>, SendError<_>>>()
                 .map_err(|_| ActorError::DropSend {
-                    msg: Who::lite(self),
-                    source: flume::SendError(()),
-                })?;
+                    msg: Who::who(self),
+                    source: SendError(()),
+                })?
+                .into_iter()
+                .sum();
+            if dropped > 0 {
+                log::debug!("{} dropped {dropped} data", Who::highlight(self));
+                self.dropped += dropped;
+            }
+            for reader in &self.readers {
+                if self.blocking {
+                    let (reader, data) = (Arc::clone(reader), data.clone());
+                    offload(move || reader.blocking_lock().read(data)).await;
+                } else {
+                    reader.lock().await.read(data.clone());
+                }
+            }
             log::debug!("{} sent ({})", Who::highlight(self), type_name::<C>());
+            for probe in self.probes.iter_mut() {
+                probe.record(data).await;
+            }
             Ok(())
         } else {
             for tx in &self.tx {
                 drop(tx);
             }
-            Err(ActorError::Disconnected(Who::lite(self)))
+            Err(ActorError::Disconnected(Who::who(self)))
         }
     }
     /// Bootstraps output
@@ -175,7 +333,7 @@ where
         Who::highlight(self)
     }
     fn len(&self) -> usize {
-        self.tx.len()
+        self.tx.len() + self.readers.len()
     }
     fn set_hash(&mut self, hash: u64) {
         self.hash = hash;
@@ -183,6 +341,26 @@ where
     fn get_hash(&self) -> u64 {
         self.hash
     }
+    fn fill(&self) -> Vec<Fill> {
+        self.tx
+            .iter()
+            .map(|tx| Fill(tx.len(), tx.capacity()))
+            .collect()
+    }
+    fn gauge(&self) -> Gauge {
+        let tx = self.tx.clone();
+        Gauge::new(move || tx.iter().map(|tx| Fill(tx.len(), tx.capacity())).collect())
+    }
+    fn dropped(&self) -> usize {
+        self.dropped
+    }
+    fn set_clock(&mut self, clock: Clock, n_sent: usize) {
+        self.clock = clock;
+        self.n_sent = n_sent;
+    }
+    fn set_blocking(&mut self) {
+        self.blocking = true;
+    }
     fn as_any(&self) -> &dyn Any {
         self
     }
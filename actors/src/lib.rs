@@ -49,6 +49,7 @@ A client must be assigned to an [Actor]
 and the client must implement some of the following traits:
  - [write](crate::io::Write) if the actor has some outputs,
  - [read](crate::io::Read) if the actor has some inputs,
+ - [read event](gmt_dos_clients::interface::ReadEvent) for the inputs of an event-driven [Reactor],
  - [update](Update), this trait must always be implemented (but the default empty implementation is acceptable)
 
 ## Model
@@ -62,8 +63,17 @@ An integrated model is build as follows:
 
 For more detailed explanations and examples, check the [actor] and [mod@model] modules.
 
+A group of actors can be reused as a single block of a model with a [subsystem::SubSystem].
+
 ## Features
 
+ * `tracing`: emits [tracing](https://docs.rs/tracing) spans for the actors loop and
+   provides a subscriber writing the spans to a [Chrome trace](trace) file
+ * `loader`: builds a model from a TOML or YAML [description](loader) of the actors and of their connections
+ * `checkpoint`: saves a [snapshot](model::checkpoint) of a running model and resumes a new model from it
+ * `replay`: [records](replay::Recorder) the messages of selected channels and [replays](replay::Replay) them to a single client
+ * `transport`: links actors of models running in different processes over TCP or Unix sockets with the [transport] endpoints
+
 */
 
 use std::sync::Arc;
@@ -74,14 +84,27 @@ pub mod actor;
 // pub mod clients;
 pub mod io;
 pub mod model;
+pub mod subsystem;
+#[cfg(feature = "tracing")]
+pub mod trace;
+#[cfg(feature = "loader")]
+pub mod loader;
+#[cfg(feature = "replay")]
+pub mod replay;
+#[cfg(feature = "transport")]
+pub mod transport;
 #[doc(inline)]
-pub use actor::{Actor, Initiator, Task, Terminator};
+pub use actor::{Actor, DynActor, Fused, Initiator, LinkPort, Reactor, Task, Terminator};
 mod network;
+mod uid;
 pub(crate) use gmt_dos_clients::interface::{
     self, print_info, Assoc, Data, Read, UniqueIdentifier, Update, Who,
 };
 pub(crate) use network::ActorOutputBuilder;
-pub use network::{AddOuput, IntoInputs, IntoLogs, IntoLogsN, TryIntoInputs};
+pub use network::{
+    AddOuput, InputMode, InputPort, InputsRate, IntoInputs, IntoLogs, IntoLogsN, OutputPort,
+    Overflow, TryIntoInputs,
+};
 
 #[derive(thiserror::Error, Debug)]
 pub enum ActorError {
@@ -97,6 +120,8 @@ pub enum ActorError {
     },
     #[error("no new data produced")]
     NoData,
+    #[error("stopped by the model handle")]
+    Stopped,
     #[error("no inputs defined")]
     NoInputs,
     #[error("no outputs defined")]
@@ -115,6 +140,31 @@ pub enum ActorError {
     NoOutputsPositiveRate(String),
     #[error(r#"Orphan output "{0}" in "{1}" actor"#)]
     OrphanOutput(String, String),
+    #[error(r#""{uid}" data of step {step} received after the data of step {previous}"#)]
+    Unordered {
+        uid: String,
+        step: usize,
+        previous: usize,
+    },
+    #[error("inputs are not aligned: {0}")]
+    Misaligned(String),
+    #[error("{0} has some linked inputs but is not in the fused block of the actors they are linked to")]
+    Unfused(String),
+    #[error(r#""{0}" is stepped before "{1}" in the fused block, but its input "{2}" comes from "{1}""#)]
+    FusedOrder(String, String, String),
+    #[error(r#"the actors of the fused block "{0}" have different rates"#)]
+    FusedRates(String),
+    #[error(r#"no output "{0}" in "{1}" actor to probe"#)]
+    NoProbedOutput(String, String),
+    #[error(r#"no input "{0}" in "{1}" actor"#)]
+    NoInput(String, String),
+    #[error("{0} has no input waiting for its data")]
+    NoWaitingInput(String),
+    #[error("{0} has some inputs that are not events, the inputs of a reactor must be connected as events")]
+    NoEventInputs(String),
+    #[cfg(feature = "transport")]
+    #[error(transparent)]
+    Transport(#[from] transport::TransportError),
 }
 pub type Result<R> = std::result::Result<R, ActorError>;
 
@@ -136,8 +186,11 @@ pub mod macros;
 
 pub mod prelude {
     pub use super::{
-        model, model::Model, Actor, AddOuput, ArcMutex, Initiator, IntoInputs, IntoLogs, IntoLogsN,
-        Task, Terminator, TryIntoInputs,
+        model,
+        model::Model,
+        subsystem::{SubSystem, TryIntoPorts},
+        Actor, AddOuput, ArcMutex, DynActor, Fused, Initiator, InputMode, IntoInputs, IntoLogs,
+        IntoLogsN, Overflow, Reactor, Task, Terminator, TryIntoInputs,
     };
     pub use vec_box::vec_box;
 }
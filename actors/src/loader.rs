@@ -0,0 +1,432 @@
+/*!
+# Declarative model
+
+A [Model] can be described in a TOML or YAML file instead of being wired in Rust.
+The description lists the actors with the name of their client, the client parameters and the actor rates,
+and the connections between the actors outputs and inputs given by the name of the UIDs,
+without the module paths (e.g. `Foo<Bar>` for `a::Foo<b::Bar>`).
+
+The clients and the UIDs they can read or write are registered in a [Registry],
+the registry is then used to build the [Model] from the description.
+
+If the rates of an actor are not given, they are set to 1 or to 0 if the actor has respectively some inputs (outputs) or none.
+
+# Example
+
+```
+use gmt_dos_actors::{loader::{ModelDescription, Registry}, prelude::*};
+use gmt_dos_clients::{interface::UID, Logging, Signals};
+# tokio_test::block_on(async {
+#[derive(UID)]
+enum Source {}
+
+let mut registry = Registry::new();
+registry
+    .client("Signals", |(n, n_step): (usize, usize)| Signals::new(n, n_step))
+    .output::<Source>();
+registry
+    .client("Logging", |n: usize| Logging::<f64>::new(n))
+    .input::<Source>();
+
+let description = ModelDescription::from_toml(
+    r#"
+name = "signals-logging"
+
+[[actors]]
+name = "source"
+client = "Signals"
+params = [1, 100]
+
+[[actors]]
+name = "sink"
+client = "Logging"
+params = 1
+
+[[connections]]
+from = "source"
+uid = "Source"
+to = ["sink"]
+"#,
+)?;
+let (model, clients) = registry.build(&description)?;
+model.check()?.run().await?;
+
+let logging = clients.get::<Logging<f64>>("sink").unwrap();
+assert_eq!(logging.lock().await.len(), 100);
+# Ok::<(), Box<dyn std::error::Error>>(())
+# });
+```
+*/
+
+use crate::{
+    interface::{Read, UniqueIdentifier, Update, Write},
+    model::{Model, Unknown},
+    network::{InputMode, Overflow},
+    uid::uid_short_name,
+    ArcMutex, DynActor, Task,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{any::Any, collections::HashMap, fs, marker::PhantomData, path::Path, sync::Arc};
+use tokio::sync::Mutex;
+
+#[derive(Debug, thiserror::Error)]
+pub enum LoaderError {
+    #[error("failed to read the model description")]
+    Io(#[from] std::io::Error),
+    #[error("failed to parse the TOML model description")]
+    Toml(#[from] toml::de::Error),
+    #[error("failed to parse the YAML model description")]
+    Yaml(#[from] serde_yaml::Error),
+    #[error(r#"unknown model description file extension "{0}", expected toml, yaml or yml"#)]
+    Extension(String),
+    #[error(r#"client "{client}" of actor "{actor}" is not registered"#)]
+    UnknownClient { actor: String, client: String },
+    #[error(r#"actor "{0}" is described more than once"#)]
+    DuplicatedActor(String),
+    #[error(r#"actor "{0}" in the connections is not described"#)]
+    UnknownActor(String),
+    #[error(r#"invalid parameters for actor "{actor}""#)]
+    Params {
+        actor: String,
+        source: serde_json::Error,
+    },
+    #[error(r#"output "{uid}" is not registered for the client of actor "{actor}""#)]
+    UnknownOutput { actor: String, uid: String },
+    #[error(r#"input "{uid}" is not registered for the client of actor "{actor}""#)]
+    UnknownInput { actor: String, uid: String },
+    #[error(r#"output "{uid}" of actor "{from}" and input "{uid}" of actor "{to}" have different types"#)]
+    UidMismatch {
+        uid: String,
+        from: String,
+        to: String,
+    },
+    #[error(r#"output "{uid}" of actor "{actor}" has a capacity of {capacity}, expected a positive capacity of bounded channels"#)]
+    Capacity {
+        actor: String,
+        uid: String,
+        capacity: usize,
+    },
+}
+pub type Result<T> = std::result::Result<T, LoaderError>;
+
+/// Model description
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModelDescription {
+    /// Model name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Actors
+    #[serde(default)]
+    pub actors: Vec<ActorDescription>,
+    /// Connections between actors outputs and inputs
+    #[serde(default)]
+    pub connections: Vec<Connection>,
+}
+impl ModelDescription {
+    /// Parses a TOML model description
+    pub fn from_toml(description: &str) -> Result<Self> {
+        Ok(toml::from_str(description)?)
+    }
+    /// Parses a YAML model description
+    pub fn from_yaml(description: &str) -> Result<Self> {
+        Ok(serde_yaml::from_str(description)?)
+    }
+    /// Reads a TOML (`.toml`) or YAML (`.yaml` or `.yml`) model description file
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let description = fs::read_to_string(path)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml(&description),
+            Some("yaml" | "yml") => Self::from_yaml(&description),
+            ext => Err(LoaderError::Extension(ext.unwrap_or_default().to_string())),
+        }
+    }
+}
+
+/// Actor description
+#[derive(Debug, Clone, Deserialize)]
+pub struct ActorDescription {
+    /// Actor name, must be unique
+    pub name: String,
+    /// Client name in the [Registry]
+    pub client: String,
+    /// Parameters passed to the client constructor
+    #[serde(default)]
+    pub params: serde_json::Value,
+    /// Inputs rate
+    pub inputs_rate: Option<usize>,
+    /// Outputs rate
+    pub outputs_rate: Option<usize>,
+    /// Calls the client methods from the threads dedicated to blocking operations
+    #[serde(default)]
+    pub blocking: bool,
+}
+
+/// Connection between the output of an actor and the inputs of other actors
+#[derive(Debug, Clone, Deserialize)]
+pub struct Connection {
+    /// Name of the actor the output belongs to
+    pub from: String,
+    /// Name of the output UID
+    pub uid: String,
+    /// Names of the actors the inputs belong to
+    pub to: Vec<String>,
+    /// Bootstraps the output
+    #[serde(default)]
+    pub bootstrap: bool,
+    /// Uses unbounded channels
+    #[serde(default)]
+    pub unbounded: bool,
+    /// Capacity of the bounded channels, positive and not given with unbounded channels
+    pub capacity: Option<usize>,
+    /// Policy of the output when a channel is full: `block`, `drop-oldest`, `drop-newest` or `latest`
+    #[serde(default)]
+    pub overflow: Overflow,
+    /// Mode of the inputs: `wait`, `optional` or `latest`
+    #[serde(default)]
+    pub mode: InputMode,
+}
+
+type Boxed = Box<dyn Any + Send>;
+type Constructor = Box<dyn Fn(&ActorDescription, usize, usize) -> Result<(Boxed, Boxed)>>;
+type OutputConnector = Box<dyn Fn(&mut Boxed, &Connection) -> (u64, Vec<Boxed>)>;
+type InputConnector = Box<dyn Fn(&mut Boxed, Boxed, u64, InputMode) -> std::result::Result<(), ()>>;
+
+struct Entry {
+    constructor: Constructor,
+    into_task: fn(Boxed) -> Box<dyn Task>,
+    outputs: HashMap<String, OutputConnector>,
+    inputs: HashMap<String, InputConnector>,
+}
+
+/// Clients and UIDs registry
+#[derive(Default)]
+pub struct Registry {
+    entries: HashMap<String, Entry>,
+}
+
+/// Registry entry of a client
+///
+/// The UIDs the client can write to or read from are added with [output](ClientEntry::output) and [input](ClientEntry::input)
+pub struct ClientEntry<'a, C> {
+    entry: &'a mut Entry,
+    client: PhantomData<C>,
+}
+
+fn into_task<C>(actor: Boxed) -> Box<dyn Task>
+where
+    C: 'static + Update + Send,
+{
+    Box::new(*actor.downcast::<DynActor<C>>().unwrap())
+}
+
+impl Registry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Registers a client under `name` with the client constructor
+    ///
+    /// The constructor argument is deserialized from the actor parameters
+    pub fn client<C, P, F>(&mut self, name: &str, constructor: F) -> ClientEntry<'_, C>
+    where
+        C: 'static + Update + Send,
+        P: DeserializeOwned,
+        F: 'static + Fn(P) -> C,
+    {
+        let constructor: Constructor = Box::new(move |description, ni, no| {
+            let params =
+                P::deserialize(&description.params).map_err(|source| LoaderError::Params {
+                    actor: description.name.clone(),
+                    source,
+                })?;
+            let client = constructor(params).into_arcx();
+            let mut actor = DynActor::new(client.clone(), ni, no).name(description.name.as_str());
+            if description.blocking {
+                actor = actor.blocking();
+            }
+            Ok((Box::new(actor) as Boxed, Box::new(client) as Boxed))
+        });
+        let entry = Entry {
+            constructor,
+            into_task: into_task::<C>,
+            outputs: HashMap::new(),
+            inputs: HashMap::new(),
+        };
+        self.entries.insert(name.to_string(), entry);
+        ClientEntry {
+            entry: self.entries.get_mut(name).unwrap(),
+            client: PhantomData,
+        }
+    }
+    /// Builds the model and its clients from the model description
+    pub fn build(&self, description: &ModelDescription) -> Result<(Model<Unknown>, Clients)> {
+        let mut actors: Vec<(String, &Entry, Boxed)> = vec![];
+        let mut clients = Clients::default();
+        for actor in &description.actors {
+            if clients.0.contains_key(&actor.name) {
+                return Err(LoaderError::DuplicatedActor(actor.name.clone()));
+            }
+            let entry =
+                self.entries
+                    .get(&actor.client)
+                    .ok_or_else(|| LoaderError::UnknownClient {
+                        actor: actor.name.clone(),
+                        client: actor.client.clone(),
+                    })?;
+            let has_inputs = description
+                .connections
+                .iter()
+                .any(|connection| connection.to.contains(&actor.name));
+            let has_outputs = description
+                .connections
+                .iter()
+                .any(|connection| connection.from == actor.name);
+            let ni = actor.inputs_rate.unwrap_or(has_inputs as usize);
+            let no = actor.outputs_rate.unwrap_or(has_outputs as usize);
+            let (dyn_actor, client) = (entry.constructor)(actor, ni, no)?;
+            clients.0.insert(actor.name.clone(), client);
+            actors.push((actor.name.clone(), entry, dyn_actor));
+        }
+
+        let index: HashMap<String, usize> = actors
+            .iter()
+            .enumerate()
+            .map(|(i, (name, ..))| (name.clone(), i))
+            .collect();
+        let position = |name: &String| {
+            index
+                .get(name)
+                .copied()
+                .ok_or_else(|| LoaderError::UnknownActor(name.clone()))
+        };
+        for connection in &description.connections {
+            if let Some(capacity) = connection.capacity {
+                if capacity == 0 || connection.unbounded {
+                    return Err(LoaderError::Capacity {
+                        actor: connection.from.clone(),
+                        uid: connection.uid.clone(),
+                        capacity,
+                    });
+                }
+            }
+            let from = position(&connection.from)?;
+            let to = connection
+                .to
+                .iter()
+                .map(position)
+                .collect::<Result<Vec<_>>>()?;
+            let (_, entry, actor) = &mut actors[from];
+            let output =
+                entry
+                    .outputs
+                    .get(&connection.uid)
+                    .ok_or_else(|| LoaderError::UnknownOutput {
+                        actor: connection.from.clone(),
+                        uid: connection.uid.clone(),
+                    })?;
+            let (hash, rxs) = output(actor, connection);
+            for (i, rx) in to.into_iter().zip(rxs) {
+                let (name, entry, actor) = &mut actors[i];
+                let input =
+                    entry
+                        .inputs
+                        .get(&connection.uid)
+                        .ok_or_else(|| LoaderError::UnknownInput {
+                            actor: name.clone(),
+                            uid: connection.uid.clone(),
+                        })?;
+                input(actor, rx, hash, connection.mode).map_err(|_| LoaderError::UidMismatch {
+                    uid: connection.uid.clone(),
+                    from: connection.from.clone(),
+                    to: name.clone(),
+                })?;
+            }
+        }
+
+        let model = Model::new(
+            actors
+                .into_iter()
+                .map(|(_, entry, actor)| (entry.into_task)(actor))
+                .collect(),
+        );
+        let model = match &description.name {
+            Some(name) => model.name(name),
+            None => model,
+        };
+        Ok((model, clients))
+    }
+}
+
+impl<'a, C> ClientEntry<'a, C>
+where
+    C: 'static + Update + Send,
+{
+    /// Registers an output of the client
+    pub fn output<U>(self) -> Self
+    where
+        C: Write<U>,
+        U: 'static + Send + Sync + UniqueIdentifier,
+        <U as UniqueIdentifier>::DataType: Send + Sync,
+    {
+        let connector: OutputConnector = Box::new(|actor, connection| {
+            let actor = actor.downcast_mut::<DynActor<C>>().unwrap();
+            let mut output = actor
+                .add_output()
+                .multiplex(connection.to.len())
+                .overflow(connection.overflow);
+            if let Some(capacity) = connection.capacity {
+                output = output.capacity(capacity);
+            }
+            if connection.bootstrap {
+                output = output.bootstrap();
+            }
+            if connection.unbounded {
+                output = output.unbounded();
+            }
+            let (hash, rxs) = output.build::<U>().unwrap_err().into_parts();
+            (
+                hash,
+                rxs.into_iter().map(|rx| Box::new(rx) as Boxed).collect(),
+            )
+        });
+        self.entry.outputs.insert(uid_short_name::<U>(), connector);
+        self
+    }
+    /// Registers an input of the client
+    pub fn input<U>(self) -> Self
+    where
+        C: Read<U>,
+        U: 'static + Send + Sync + UniqueIdentifier,
+        <U as UniqueIdentifier>::DataType: Send + Sync,
+    {
+        let connector: InputConnector = Box::new(|actor, rx, hash, mode| {
+            let actor = actor.downcast_mut::<DynActor<C>>().unwrap();
+            let rx = rx
+                .downcast::<flume::Receiver<crate::Data<U>>>()
+                .map_err(|_| ())?;
+            actor.add_input_with_mode(*rx, hash, mode);
+            Ok(())
+        });
+        self.entry.inputs.insert(uid_short_name::<U>(), connector);
+        self
+    }
+}
+
+/// Clients of the actors built from a [ModelDescription]
+#[derive(Default)]
+pub struct Clients(HashMap<String, Boxed>);
+impl Clients {
+    /// Returns the client of the actor `name`
+    ///
+    /// Returns [None] if there is no such actor or if the client is not of type `C`
+    pub fn get<C>(&self, name: &str) -> Option<Arc<Mutex<C>>>
+    where
+        C: 'static + Update + Send,
+    {
+        self.0
+            .get(name)
+            .and_then(|client| client.downcast_ref::<Arc<Mutex<C>>>())
+            .cloned()
+    }
+}
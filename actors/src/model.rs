@@ -102,6 +102,108 @@ Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)])
 # Ok::<(), gmt_dos_actors::model::ModelError>(())
 # });
 ```
+A running model can be paused, resumed, stopped or aborted with its [ModelHandle]:
+```
+# tokio_test::block_on(async {
+# use gmt_dos_actors::prelude::*;
+# use gmt_dos_clients::{interface::UID, Logging, Sampler, Signals};
+# let mut source: Initiator<_> = Signals::new(1, 100).into();
+# #[derive(UID)]
+# enum Source {};
+# let mut sampler: Actor<_> = Sampler::<Vec<f64>, Source>::default().into();
+# let logging = Logging::<f64>::default().into_arcx();
+# let mut sink = Terminator::<_>::new(logging.clone());
+# source.add_output().build::<Source>().into_input(&mut sampler);
+# sampler.add_output().build::<Source>().into_input(&mut sink);
+let model = Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)])
+       .check()?
+       .run();
+let handle = model.handle();
+handle.stop();
+model.await?;
+# Ok::<(), gmt_dos_actors::model::ModelError>(())
+# });
+```
+A watchdog aborts a running model that stalls, reporting the channels the actors are waiting on:
+```
+# tokio_test::block_on(async {
+# use gmt_dos_actors::prelude::*;
+# use gmt_dos_clients::{interface::UID, Logging, Sampler, Signals};
+# let mut source: Initiator<_> = Signals::new(1, 100).into();
+# #[derive(UID)]
+# enum Source {};
+# let mut sampler: Actor<_> = Sampler::<Vec<f64>, Source>::default().into();
+# let logging = Logging::<f64>::default().into_arcx();
+# let mut sink = Terminator::<_>::new(logging.clone());
+# source.add_output().build::<Source>().into_input(&mut sampler);
+# sampler.add_output().build::<Source>().into_input(&mut sink);
+Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)])
+       .check()?
+       .run()
+       .watchdog(std::time::Duration::from_secs(60))
+       .await?;
+# Ok::<(), gmt_dos_actors::model::ModelError>(())
+# });
+```
+The actor tasks can also be stepped one after the other on a single thread,
+in the topological order of the model network, for reproducible runs:
+```
+# use gmt_dos_actors::prelude::*;
+# use gmt_dos_clients::{interface::UID, Logging, Sampler, Signals};
+# let mut source: Initiator<_> = Signals::new(1, 100).into();
+# #[derive(UID)]
+# enum Source {};
+# let mut sampler: Actor<_> = Sampler::<Vec<f64>, Source>::default().into();
+# let logging = Logging::<f64>::default().into_arcx();
+# let mut sink = Terminator::<_>::new(logging.clone());
+# source.add_output().build::<Source>().into_input(&mut sampler);
+# sampler.add_output().build::<Source>().into_input(&mut sink);
+let model = Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)])
+       .check()?
+       .run_lockstep()?;
+println!("{}", model.trace().unwrap());
+# Ok::<(), gmt_dos_actors::model::ModelError>(())
+```
+The completed model reports how and after how many steps each actor exited,
+and `check` on a completed model turns abnormal exits into an error:
+```
+# tokio_test::block_on(async {
+# use gmt_dos_actors::prelude::*;
+# use gmt_dos_clients::{interface::UID, Logging, Sampler, Signals};
+# let mut source: Initiator<_> = Signals::new(1, 100).into();
+# #[derive(UID)]
+# enum Source {};
+# let mut sampler: Actor<_> = Sampler::<Vec<f64>, Source>::default().into();
+# let logging = Logging::<f64>::default().into_arcx();
+# let mut sink = Terminator::<_>::new(logging.clone());
+# source.add_output().build::<Source>().into_input(&mut sampler);
+# sampler.add_output().build::<Source>().into_input(&mut sink);
+let model = Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)])
+       .check()?
+       .run()
+       .await?;
+println!("{}", model.report().unwrap());
+model.check()?;
+# Ok::<(), gmt_dos_actors::model::ModelError>(())
+# });
+```
+The model network is exported as a JSON document or as a [Mermaid](https://mermaid.js.org/) flowchart with the model [Graph]:
+```
+# use gmt_dos_actors::prelude::*;
+# use gmt_dos_clients::{interface::UID, Logging, Sampler, Signals};
+# let mut source: Initiator<_> = Signals::new(1, 100).into();
+# #[derive(UID)]
+# enum Source {};
+# let mut sampler: Actor<_> = Sampler::<Vec<f64>, Source>::default().into();
+# let logging = Logging::<f64>::default().into_arcx();
+# let mut sink = Terminator::<_>::new(logging.clone());
+# source.add_output().build::<Source>().into_input(&mut sampler);
+# sampler.add_output().build::<Source>().into_input(&mut sink);
+let model = Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)]);
+let graph = model.graph().unwrap();
+println!("{}", graph.as_json());
+println!("{}", graph.as_mermaid());
+```
 Once the model run to completion, the data from `logging` is read with:
 ```
 # tokio_test::block_on(async {
@@ -135,11 +237,34 @@ let data: &[f64]  = &logging.lock().await;
 [Logging]: crate::clients::Logging
 */
 
-use crate::Task;
-use std::{env, fmt::Display, marker::PhantomData, path::Path, process::Command, time::Instant};
+use crate::{actor::Monitor, Task};
+use std::{
+    env, fmt::Display, marker::PhantomData, path::Path, process::Command, sync::Arc,
+    time::Instant,
+};
 
 mod flowchart;
 pub use flowchart::Graph;
+mod lockstep;
+pub use lockstep::Trace;
+mod control;
+#[doc(hidden)]
+pub use control::Control;
+pub use control::ModelHandle;
+mod report;
+pub use report::{ActorReport, ExitReason, ModelReport};
+mod validation;
+mod watchdog;
+mod profile;
+pub use profile::{Profile, ProfileSummary, Timing};
+#[cfg(feature = "checkpoint")]
+pub mod checkpoint;
+#[cfg(feature = "checkpoint")]
+#[doc(inline)]
+pub use checkpoint::{ActorSnapshot, CheckpointError, Snapshot};
+#[cfg(feature = "checkpoint")]
+#[doc(hidden)]
+pub use checkpoint::{ClientState, InputQueue};
 
 #[derive(thiserror::Error, Debug)]
 pub enum ModelError {
@@ -149,6 +274,35 @@ pub enum ModelError {
     TaskError(#[from] tokio::task::JoinError),
     #[error("Actor IO inconsistency")]
     ActorIO(#[from] crate::ActorError),
+    #[error("lockstep scheduler stalled, waiting on {0:?}")]
+    Deadlock(Vec<String>),
+    #[error("lockstep scheduler thread panicked")]
+    LockstepPanic,
+    #[error("{actor} panicked at step {step}: {msg}")]
+    ActorPanicked {
+        actor: String,
+        step: usize,
+        msg: String,
+    },
+    #[error("some actors exited abnormally: {0:#?}")]
+    AbnormalExit(Vec<String>),
+    #[error("outputs not connected to any input: {0:#?}")]
+    DanglingOutputs(Vec<String>),
+    #[error("inputs connected to actors missing from the model: {0:#?}")]
+    MissingSenders(Vec<String>),
+    #[error("duplicated connections: {0:#?}")]
+    DuplicatedConnections(Vec<String>),
+    #[error("feedback loops without bootstrapped output: {0:#?}")]
+    UnbootstrappedLoops(Vec<String>),
+    #[error("outputs and inputs rates don't match: {0:#?}")]
+    RateMismatch(Vec<String>),
+    #[error("feedback loops with inconsistent rates: {0:#?}")]
+    InconsistentLoopRates(Vec<String>),
+    #[error("model stalled and aborted by the watchdog:\n{0}")]
+    Stalled(String),
+    #[cfg(feature = "checkpoint")]
+    #[error("model checkpoint failed")]
+    Checkpoint(#[from] CheckpointError),
 }
 
 type Result<T> = std::result::Result<T, ModelError>;
@@ -164,13 +318,30 @@ pub enum Completed {}
 
 type Actors = Vec<Box<dyn Task>>;
 
+/// Actor task spawned by a running [Model]
+struct TaskHandle {
+    name: String,
+    monitor: Arc<Monitor>,
+    join_handle: tokio::task::JoinHandle<ActorReport>,
+    #[cfg(feature = "checkpoint")]
+    inputs_hashes: Vec<u64>,
+    #[cfg(feature = "checkpoint")]
+    client_state: Option<ClientState>,
+    #[cfg(feature = "checkpoint")]
+    input_queues: Vec<Box<dyn InputQueue>>,
+}
+
 /// Actor model
 pub struct Model<State> {
     name: Option<String>,
     actors: Option<Actors>,
-    task_handles: Option<Vec<tokio::task::JoinHandle<()>>>,
+    task_handles: Option<Vec<TaskHandle>>,
     state: PhantomData<State>,
     start: Instant,
+    trace: Option<Trace>,
+    handle: Option<ModelHandle>,
+    report: Option<ModelReport>,
+    watchdog: Option<watchdog::Watchdog>,
 }
 
 impl<S> Display for Model<S> {
@@ -231,12 +402,15 @@ where
     pub fn graph(&self) -> Option<Graph> {
         self.actors
             .as_ref()
-            .map(|actors| Graph::new(actors.iter().map(|a| a.as_plain()).collect()))
+            .map(|actors| Graph::new(actors.iter().flat_map(|a| a.as_plains()).collect()))
     }
     /// Produces the model flowchart from [Graph]
     ///
-    /// The flowchart is written to the SVG file "integrated_model.dot.svg".
-    /// If a different model `name` is set, the file gets written to "`name`.dot.svg"
+    /// The flowchart is written to the SVG file "integrated_model.svg".
+    /// If a different model `name` is set, the file gets written to "`name`.svg"
+    ///
+    /// If the environment variable `ACTORS_GRAPH` is set to a [Graphviz](https://www.graphviz.org/) layout program (e.g. `neato`),
+    /// the flowchart is written instead to the Graphviz dot file "`name`.dot" and converted to the SVG file "`name`.dot.svg"
     pub fn flowchart(self) -> Self {
         let name = self
             .name
@@ -244,29 +418,34 @@ where
             .unwrap_or_else(|| "integrated_model".to_string());
         let root_env = env::var("DATA_REPO").unwrap_or_else(|_| ".".to_string());
         let path = Path::new(&root_env).join(&name);
-        if let Some(graph) = self.graph() {
-            match graph.to_dot(path.with_extension("dot")) {
-                Ok(_) => {
-                    if let Err(e) =
-                        Command::new(env::var("ACTORS_GRAPH").unwrap_or("neato".to_string()))
-                            .arg("-Gstart=rand")
-                            .arg("-Tsvg")
-                            .arg("-O")
-                            .arg(path.with_extension("dot").to_str().unwrap())
-                            .output()
-                    {
-                        println!(
-                            "Failed to convert Graphviz dot file {path:?} to SVG image with {e}"
-                        )
-                    }
+        let Some(graph) = self.graph() else {
+            return self;
+        };
+        let Ok(program) = env::var("ACTORS_GRAPH") else {
+            if let Err(e) = graph.to_svg(path.with_extension("svg")) {
+                println!("Failed to write SVG flowchart {path:?} with {e}")
+            }
+            return self;
+        };
+        match graph.to_dot(path.with_extension("dot")) {
+            Ok(_) => {
+                if let Err(e) = Command::new(program)
+                    .arg("-Gstart=rand")
+                    .arg("-Tsvg")
+                    .arg("-O")
+                    .arg(path.with_extension("dot").to_str().unwrap())
+                    .output()
+                {
+                    println!("Failed to convert Graphviz dot file {path:?} to SVG image with {e}")
                 }
-                Err(e) => println!("Failed to write Graphviz dot file {path:?} with {e}"),
             }
+            Err(e) => println!("Failed to write Graphviz dot file {path:?} with {e}"),
         }
         self
     }
 }
 
+pub mod completed;
 pub mod ready;
 pub mod running;
 pub mod unknown;
@@ -0,0 +1,314 @@
+/*!
+# Model checkpoint
+
+A [running](super::Running) model is checkpointed at a given simulation step with [Model::checkpoint](super::Model::checkpoint).
+Each actor is paused at the start of the cycle following the step or waits for the inputs of another paused actor,
+the data already received by an actor waiting on some other inputs being part of its client state.
+At this barrier, the state of each client that has been flagged with [Actor::checkpoint](crate::Actor::checkpoint)
+is serialized into a [Snapshot], together with the number of updates of each actor and the inputs already received.
+The data still in the inputs channels, like the data of the bootstrapped outputs of feedback loops,
+is serialized as well for the inputs flagged with [Actor::checkpoint_input](crate::Actor::checkpoint_input).
+
+The model remains paused until it is either resumed or stopped with its [ModelHandle](super::ModelHandle).
+
+A new model, built with the same actors in the same order, resumes from the [Snapshot] with [Model::resume](super::Model::resume):
+the bootstrapping of the outputs is skipped and the actors loop restarts where it has been paused,
+producing the same results as the model that has been checkpointed.
+
+The clients must implement the [Checkpoint](gmt_dos_clients::interface::Checkpoint) trait,
+actors without checkpointed clients are resumed with their clients as they are.
+
+```
+# tokio_test::block_on(async {
+use gmt_dos_actors::{model::Snapshot, prelude::*};
+use gmt_dos_clients::{interface::UID, Logging, Signals};
+#[derive(UID)]
+enum Source {};
+let build = || {
+    let mut source: Initiator<_> = Actor::from(Signals::new(1, 100)).checkpoint();
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone()).checkpoint();
+    source.add_output().build::<Source>().into_input(&mut sink)?;
+    Ok::<_, Box<dyn std::error::Error>>((model!(source, sink), logging))
+};
+
+let (model, _) = build()?;
+let model = model.check()?.run();
+let snapshot = model.checkpoint(40).await?;
+model.handle().stop();
+model.await?;
+# let path = std::env::temp_dir().join("snapshot.bin");
+snapshot.to_path(&path)?;
+
+let (model, logging) = build()?;
+model.resume(&Snapshot::from_path(&path)?)?.check()?.run().await?;
+assert_eq!(logging.lock().await.len(), 100);
+# Ok::<(), Box<dyn std::error::Error>>(())
+# });
+```
+*/
+
+use super::{Model, ModelError, Result, Running, Unknown};
+use crate::actor::Wait;
+use futures::future::select_all;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    future::Future,
+    io::{BufReader, BufWriter},
+    path::Path,
+    pin::Pin,
+    time::Duration,
+};
+
+/// Client state serializer
+#[doc(hidden)]
+pub type ClientState =
+    Box<dyn Fn() -> Pin<Box<dyn Future<Output = bincode::Result<Vec<u8>>> + Send>> + Send + Sync>;
+
+/// Data queued on an actor input
+#[doc(hidden)]
+pub trait InputQueue: Send + Sync {
+    /// Returns the input hash
+    fn hash(&self) -> u64;
+    /// Checks if the input waits on its channel
+    fn is_receiving(&self) -> bool;
+    /// Moves the data of the input channel into the queue, unless the input is receiving
+    ///
+    /// Returns the number of data taken from the channel
+    fn drain(&self) -> usize;
+    /// Serializes the data of the queue
+    fn save(&self) -> std::result::Result<Vec<Vec<u8>>, CheckpointError>;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CheckpointError {
+    #[error("failed to read or to write the snapshot")]
+    Io(#[from] std::io::Error),
+    #[error("failed to serialize or to deserialize the snapshot")]
+    Bincode(#[from] bincode::Error),
+    #[error("{0} exited before the checkpoint")]
+    Exited(String),
+    #[error("{0} messages in flight at the checkpoint")]
+    InFlight(usize),
+    #[error("{0} input data is not checkpointed")]
+    Unsaved(String),
+    #[error("{1} has no input #{0} for the queued data")]
+    NoInput(u64, String),
+    #[error("the model actors {0:?} do not match the snapshot actors {1:?}")]
+    Mismatch(Vec<String>, Vec<String>),
+    #[error("{0} client is not checkpointed")]
+    NotCheckpointed(String),
+    #[error("{0} client is already in use")]
+    Locked(String),
+}
+
+/// Actor state in a [Snapshot]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActorSnapshot {
+    /// Actor name
+    pub name: String,
+    /// Number of client updates
+    pub n_step: usize,
+    /// Hashes of the inputs already received
+    pub received: Vec<u64>,
+    /// Serialized data queued on the inputs, by input hash
+    pub queued: Vec<(u64, Vec<Vec<u8>>)>,
+    /// Serialized client state, if the client is checkpointed
+    pub state: Option<Vec<u8>>,
+}
+
+/// Snapshot of a checkpointed [Model]
+///
+/// The actors are in the same order as in the model
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub actors: Vec<ActorSnapshot>,
+}
+impl Snapshot {
+    /// Writes the snapshot to a file
+    pub fn to_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<(), CheckpointError> {
+        let file = File::create(path)?;
+        bincode::serialize_into(BufWriter::new(file), self)?;
+        Ok(())
+    }
+    /// Reads a snapshot from a file
+    pub fn from_path<P: AsRef<Path>>(path: P) -> std::result::Result<Self, CheckpointError> {
+        let file = File::open(path)?;
+        Ok(bincode::deserialize_from(BufReader::new(file))?)
+    }
+}
+
+// Hashes of the inputs an actor is waiting on
+fn receiving(waits: &[Wait]) -> Vec<u64> {
+    waits
+        .iter()
+        .filter_map(|wait| match wait {
+            Wait::Recv { hash, .. } => Some(*hash),
+            _ => None,
+        })
+        .collect()
+}
+
+impl Model<Running> {
+    /// Checkpoints the model at the simulation `step`
+    ///
+    /// The model is paused at the checkpoint and it must be either resumed or stopped with its [ModelHandle](super::ModelHandle).
+    /// An error is returned if an actor exits before the checkpoint, the model then running on,
+    /// or if some data in flight at the checkpoint cannot be saved.
+    pub async fn checkpoint(&self, step: usize) -> Result<Snapshot> {
+        let task_handles = self.task_handles.as_ref().unwrap();
+        let handle = self.handle();
+        task_handles
+            .iter()
+            .for_each(|task_handle| task_handle.monitor.enable_watching());
+        handle.pause_at(step);
+        // the barrier is reached when all the actors are either paused or waiting for new inputs
+        // and all the data sent has been received, the actors state being read again
+        // until it has not changed while it was read
+        let settled = |changes: &[usize]| {
+            task_handles
+                .iter()
+                .map(|task_handle| task_handle.monitor.n_change())
+                .eq(changes.iter().cloned())
+        };
+        let waits = loop {
+            let changed: Vec<_> = task_handles
+                .iter()
+                .map(|task_handle| {
+                    let mut changed = Box::pin(task_handle.monitor.changed());
+                    changed.as_mut().enable();
+                    changed
+                })
+                .collect();
+            if let Some(task_handle) = task_handles.iter().find(|task_handle| {
+                task_handle.monitor.has_exited() || task_handle.join_handle.is_finished()
+            }) {
+                handle.resume();
+                return Err(CheckpointError::Exited(task_handle.name.clone()).into());
+            }
+            let changes: Vec<_> = task_handles
+                .iter()
+                .map(|task_handle| task_handle.monitor.n_change())
+                .collect();
+            let waits: Vec<_> = task_handles
+                .iter()
+                .map(|task_handle| task_handle.monitor.waits())
+                .collect();
+            // an actor waiting on an input may not have looked at the data queued on the input yet
+            // (the inputs of the tasks without queues are only checked for data in their channels)
+            let quiet = task_handles.iter().zip(&waits).all(|(task_handle, waits)| {
+                !waits.is_empty()
+                    && waits.iter().all(|wait| match wait {
+                        Wait::Recv { hash, fill, .. } => {
+                            fill.0 == 0
+                                && task_handle
+                                    .input_queues
+                                    .iter()
+                                    .find(|input_queue| input_queue.hash() == *hash)
+                                    .is_none_or(|input_queue| input_queue.is_receiving())
+                        }
+                        Wait::Paused | Wait::Send { .. } => true,
+                    })
+            });
+            if !quiet || !settled(&changes) {
+                select_all(changed).await;
+                continue;
+            }
+            // the data of the inputs the actors are not receiving is moved into the inputs queues,
+            // releasing the actors sending data to paused actors
+            let n_drained: usize = task_handles
+                .iter()
+                .map(|task_handle| {
+                    let n = task_handle
+                        .input_queues
+                        .iter()
+                        .map(|input_queue| input_queue.drain())
+                        .sum();
+                    task_handle.monitor.received(n);
+                    n
+                })
+                .sum();
+            let sending = waits
+                .iter()
+                .flatten()
+                .any(|wait| matches!(wait, Wait::Send { .. }));
+            let (n_sent, n_received) = task_handles.iter().fold((0, 0), |(s, r), task_handle| {
+                (
+                    s + task_handle.monitor.n_sent(),
+                    r + task_handle.monitor.n_received(),
+                )
+            });
+            if !sending && n_sent == n_received && settled(&changes) {
+                break waits;
+            }
+            if n_drained == 0
+                && tokio::time::timeout(Duration::from_millis(100), select_all(changed))
+                    .await
+                    .is_err()
+            {
+                // the actors are stuck on some data left in channels without queues
+                return Err(CheckpointError::InFlight(n_sent.saturating_sub(n_received)).into());
+            }
+        };
+        let mut actors = vec![];
+        for (task_handle, waits) in task_handles.iter().zip(waits) {
+            let mut queued = vec![];
+            for input_queue in &task_handle.input_queues {
+                let data = input_queue.save()?;
+                if !data.is_empty() {
+                    queued.push((input_queue.hash(), data));
+                }
+            }
+            // a paused actor has not yet received any of the inputs of its next cycle
+            let received = if waits.contains(&Wait::Paused) {
+                vec![]
+            } else {
+                let receiving = receiving(&waits);
+                task_handle
+                    .inputs_hashes
+                    .iter()
+                    .filter(|hash| !receiving.contains(hash))
+                    .cloned()
+                    .collect()
+            };
+            let state = match task_handle.client_state.as_ref() {
+                Some(client_state) => Some(client_state().await.map_err(CheckpointError::from)?),
+                None => None,
+            };
+            actors.push(ActorSnapshot {
+                name: task_handle.name.clone(),
+                n_step: task_handle.monitor.n_step(),
+                received,
+                queued,
+                state,
+            });
+        }
+        // the task handles are in the reverse order of the model actors
+        actors.reverse();
+        Ok(Snapshot { actors })
+    }
+}
+
+impl Model<Unknown> {
+    /// Resumes the model from a [Snapshot]
+    ///
+    /// The actors of the model must be the same and in the same order as the actors of
+    /// the model the snapshot has been taken from.
+    pub fn resume(mut self, snapshot: &Snapshot) -> Result<Self> {
+        let actors = self.actors.as_mut().ok_or(ModelError::NoActors)?;
+        let names: Vec<_> = actors.iter().map(|actor| actor.name()).collect();
+        let snapshot_names: Vec<_> = snapshot
+            .actors
+            .iter()
+            .map(|actor| actor.name.clone())
+            .collect();
+        if names != snapshot_names {
+            return Err(CheckpointError::Mismatch(names, snapshot_names).into());
+        }
+        for (actor, actor_snapshot) in actors.iter_mut().zip(&snapshot.actors) {
+            actor.restore(actor_snapshot)?;
+        }
+        Ok(self)
+    }
+}
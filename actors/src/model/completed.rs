@@ -0,0 +1,25 @@
+use super::{Completed, Model, ModelReport, ProfileSummary, Result, Trace};
+
+impl Model<Completed> {
+    /// Returns the sequence of actor steps if the model was run with [Model::run_lockstep]
+    pub fn trace(&self) -> Option<&Trace> {
+        self.trace.as_ref()
+    }
+    /// Returns the report on how each actor exited
+    pub fn report(&self) -> Option<&ModelReport> {
+        self.report.as_ref()
+    }
+    /// Returns the timing summary of the actors if the model was profiled
+    pub fn profile(&self) -> Option<ProfileSummary> {
+        self.report.as_ref().and_then(ProfileSummary::new)
+    }
+    /// Returns an error if some actors exited abnormally
+    ///
+    /// See [ModelReport::abnormal] for the definition of an abnormal exit
+    pub fn check(self) -> Result<Self> {
+        if let Some(report) = self.report.as_ref() {
+            report.check()?;
+        }
+        Ok(self)
+    }
+}
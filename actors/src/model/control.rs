@@ -0,0 +1,130 @@
+use crate::{
+    actor::Monitor,
+    ActorError, Result,
+};
+use std::sync::Arc;
+use tokio::{sync::watch, task::AbortHandle};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Command {
+    Run,
+    Pause,
+    /// Pauses once the given number of simulation steps is reached
+    PauseAt(usize),
+    Stop,
+}
+
+/// Actor side of the [ModelHandle]
+///
+/// The commands are honored by all the actors at the start of each cycle,
+/// so the actors never stop or pause in the middle of a cycle.
+#[doc(hidden)]
+#[derive(Debug, Clone)]
+pub struct Control(watch::Receiver<Command>);
+impl Control {
+    /// Waits until the model is running
+    ///
+    /// The number of simulation steps of the actor is the number of client updates
+    /// times the `rate`, the inputs rate or the outputs rate of the actors without inputs.
+    /// Returns [ActorError::Stopped] if the model has been stopped
+    pub(crate) async fn proceed(&mut self, monitor: &Monitor, rate: usize) -> Result<()> {
+        loop {
+            let command = *self.0.borrow_and_update();
+            match command {
+                Command::Run => return Ok(()),
+                Command::PauseAt(step) if monitor.n_step() * rate < step => return Ok(()),
+                Command::Stop => return Err(ActorError::Stopped),
+                Command::Pause | Command::PauseAt(_) => {
+                    monitor.set_paused(true);
+                    let changed = self.0.changed().await;
+                    monitor.set_paused(false);
+                    if changed.is_err() {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Control handle of a [running](super::Running) [Model](super::Model)
+///
+/// The handle is obtained with [Model::handle](super::Model::handle) and can be cloned
+/// and moved to other tasks or threads, like a signal handler or a watchdog.
+#[derive(Debug, Clone)]
+pub struct ModelHandle {
+    command: Arc<watch::Sender<Command>>,
+    abort_handles: Arc<Vec<AbortHandle>>,
+}
+impl ModelHandle {
+    pub(crate) fn new() -> (Self, Control) {
+        let (tx, rx) = watch::channel(Command::Run);
+        (
+            Self {
+                command: Arc::new(tx),
+                abort_handles: Arc::new(vec![]),
+            },
+            Control(rx),
+        )
+    }
+    pub(crate) fn abort_handles(self, abort_handles: Vec<AbortHandle>) -> Self {
+        Self {
+            abort_handles: Arc::new(abort_handles),
+            ..self
+        }
+    }
+    /// Stops the model
+    ///
+    /// The actors complete their current cycle and then exit,
+    /// the data still in the channels is discarded.
+    pub fn stop(&self) {
+        self.command.send_replace(Command::Stop);
+    }
+    /// Pauses the model at the start of the next cycle
+    pub fn pause(&self) {
+        self.command.send_if_modified(|command| {
+            if *command == Command::Run {
+                *command = Command::Pause;
+                true
+            } else {
+                false
+            }
+        });
+    }
+    /// Pauses each actor at the start of its cycle that follows the simulation `step`
+    pub fn pause_at(&self, step: usize) {
+        self.command.send_if_modified(|command| {
+            if *command == Command::Run {
+                *command = Command::PauseAt(step);
+                true
+            } else {
+                false
+            }
+        });
+    }
+    /// Resumes a paused model
+    pub fn resume(&self) {
+        self.command.send_if_modified(|command| {
+            if matches!(*command, Command::Pause | Command::PauseAt(_)) {
+                *command = Command::Run;
+                true
+            } else {
+                false
+            }
+        });
+    }
+    /// Cancels immediately the tasks of all the actors
+    pub fn abort(&self) {
+        self.abort_handles
+            .iter()
+            .for_each(|abort_handle| abort_handle.abort());
+    }
+    /// Checks if the model is paused or is set to pause at a given step
+    pub fn is_paused(&self) -> bool {
+        matches!(*self.command.borrow(), Command::Pause | Command::PauseAt(_))
+    }
+    /// Checks if the model has been stopped
+    pub fn is_stopped(&self) -> bool {
+        *self.command.borrow() == Command::Stop
+    }
+}
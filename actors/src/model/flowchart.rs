@@ -6,7 +6,9 @@ use std::{
     path::Path,
 };
 
-use crate::actor::PlainActor;
+use crate::actor::{plain::IO, PlainActor};
+
+mod svg;
 
 /// [Model](crate::model::Model) network mapping
 ///
@@ -14,10 +16,94 @@ use crate::actor::PlainActor;
 /// A new [Graph] is created with `Model::graph()`.
 ///
 /// The model flow chart is written to a SVG image with `neato -Gstart=rand -Tsvg filename.dot > filename.svg`
+/// or directly with [Graph::to_svg] that does not require Graphviz.
+///
+/// The network can also be exported as a JSON document with [Graph::to_json] or as a [Mermaid](https://mermaid.js.org/) flowchart with [Graph::to_mermaid],
+/// neither requires Graphviz.
+///
+/// The actors of a [SubSystem](crate::subsystem::SubSystem) are drawn inside a cluster
+/// and a cluster is replaced by a single node with [Graph::collapse].
 #[derive(Debug)]
 pub struct Graph {
     actors: Vec<PlainActor>,
 }
+
+// Connection between the output of an actor and the input of another actor
+struct Edge<'a> {
+    from: usize,
+    to: usize,
+    uid: &'a str,
+    bootstrap: bool,
+    unbounded: bool,
+}
+
+// Sub-system with the indices of its actors and its nested sub-systems
+struct Cluster<'a> {
+    name: &'a str,
+    actors: Vec<usize>,
+    clusters: Vec<Cluster<'a>>,
+}
+impl<'a> Cluster<'a> {
+    // Sub-systems tree of the actors, the actors outside any sub-system are not listed
+    fn tree(actors: &'a [PlainActor]) -> Vec<Cluster<'a>> {
+        let mut root: Vec<Cluster> = vec![];
+        for (i, actor) in actors.iter().enumerate() {
+            let mut clusters = &mut root;
+            for (depth, name) in actor.subsystem.iter().enumerate() {
+                let k = match clusters.iter().position(|cluster| cluster.name == name) {
+                    Some(k) => k,
+                    None => {
+                        clusters.push(Cluster {
+                            name,
+                            actors: vec![],
+                            clusters: vec![],
+                        });
+                        clusters.len() - 1
+                    }
+                };
+                if depth + 1 == actor.subsystem.len() {
+                    clusters[k].actors.push(i);
+                }
+                clusters = &mut clusters[k].clusters;
+            }
+        }
+        root
+    }
+    // Number of levels of nested sub-systems, including this one
+    fn depth(&self) -> usize {
+        1 + self
+            .clusters
+            .iter()
+            .map(|cluster| cluster.depth())
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+// Escapes a string for JSON
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+// Escapes a string for a Mermaid label
+fn mermaid_string(s: &str) -> String {
+    s.replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
 impl Graph {
     pub(super) fn new(actors: Vec<PlainActor>) -> Self {
         let mut hasher = DefaultHasher::new();
@@ -38,7 +124,100 @@ impl Graph {
         });
         Self { actors }
     }
+    /// Collapses the sub-systems `name` into single nodes
+    ///
+    /// The node of a collapsed sub-system keeps only the connections to actors outside of the sub-system
+    pub fn collapse(mut self, name: &str) -> Self {
+        while let Some((i, depth)) = self.actors.iter().enumerate().find_map(|(i, actor)| {
+            actor
+                .subsystem
+                .iter()
+                .position(|subsystem| subsystem == name)
+                .map(|depth| (i, depth))
+        }) {
+            let path = self.actors[i].subsystem[..=depth].to_vec();
+            let (members, others): (Vec<_>, Vec<_>) = self
+                .actors
+                .into_iter()
+                .enumerate()
+                .partition(|(_, actor)| actor.subsystem.starts_with(&path));
+            let inputs_hashes: Vec<u64> = others
+                .iter()
+                .flat_map(|(_, actor)| actor.inputs.iter().flatten().map(|input| input.get_hash()))
+                .collect();
+            let mut actor = PlainActor::collapse(
+                name.to_string(),
+                path[..depth].to_vec(),
+                members.into_iter().map(|(_, actor)| actor).collect(),
+                &inputs_hashes,
+            );
+            let mut hasher = DefaultHasher::new();
+            actor.hash(&mut hasher);
+            actor.hash = hasher.finish();
+            // the collapsed sub-system takes the place of its first actor
+            let k = others.iter().take_while(|(j, _)| *j < i).count();
+            self.actors = others.into_iter().map(|(_, actor)| actor).collect();
+            self.actors.insert(k, actor);
+        }
+        self
+    }
+    // Graphviz clusters
+    fn dot_clusters(&self, clusters: &[Cluster], n: &mut usize, indent: usize) -> String {
+        let pad = " ".repeat(indent);
+        clusters
+            .iter()
+            .map(|cluster| {
+                *n += 1;
+                let id = *n;
+                let nodes: String = cluster
+                    .actors
+                    .iter()
+                    .map(|&i| format!("{pad}  {};\n", self.actors[i].hash))
+                    .collect();
+                format!(
+                    "{pad}subgraph cluster_{} {{\n{pad}  label=\"{}\"; style=rounded; color=lightgray; fontcolor=lightgray;\n{}{}{pad}}}\n",
+                    id,
+                    cluster.name.replace('"', "\\\""),
+                    nodes,
+                    self.dot_clusters(&cluster.clusters, n, indent + 2)
+                )
+            })
+            .collect()
+    }
+    // Mermaid nodes and subgraphs
+    fn mermaid_nodes(
+        &self,
+        actors: &[usize],
+        clusters: &[Cluster],
+        n: &mut usize,
+        indent: usize,
+    ) -> String {
+        let pad = " ".repeat(indent);
+        let mut mermaid: String = actors
+            .iter()
+            .map(|&i| {
+                format!(
+                    "{pad}a{}[\"{}\"]\n",
+                    i,
+                    mermaid_string(&self.actors[i].client)
+                )
+            })
+            .collect();
+        clusters.iter().for_each(|cluster| {
+            *n += 1;
+            let id = *n;
+            mermaid.push_str(&format!(
+                "{pad}subgraph s{}[\"{}\"]\n{}{pad}end\n",
+                id,
+                mermaid_string(cluster.name),
+                self.mermaid_nodes(&cluster.actors, &cluster.clusters, n, indent + 2)
+            ))
+        });
+        mermaid
+    }
     /// Returns the diagram in the [Graphviz](https://www.graphviz.org/) dot language
+    ///
+    /// The sub-systems clusters are only drawn by the `dot` and `fdp` layout programs
     pub fn to_string(&self) -> String {
         let mut lookup: HashMap<usize, usize> = HashMap::new();
         let mut colors = (1usize..=8).cycle();
@@ -85,7 +264,7 @@ digraph  G {{
   splines = true;
   bgcolor = gray24;
   {{node [shape=box, width=1.5, style="rounded,filled", fillcolor=lightgray]; {};}}
-  node [shape=point, fillcolor=gray24, color=lightgray];
+{}  node [shape=point, fillcolor=gray24, color=lightgray];
 
   /* Outputs */
 {{
@@ -111,10 +290,160 @@ digraph  G {{
                 })
                 .collect::<Vec<String>>()
                 .join("; "),
+            self.dot_clusters(&Cluster::tree(&self.actors), &mut 0, 2),
             outputs.join("\n"),
             inputs.join("\n"),
         )
     }
+    // Connections between actors ordered by the inputs of the actors
+    fn edges(&self) -> Vec<Edge<'_>> {
+        let mut edges = vec![];
+        for (to, actor) in self.actors.iter().enumerate() {
+            for input in actor.inputs.iter().flatten() {
+                let Some((from, output)) = self.actors.iter().enumerate().find_map(|(i, actor)| {
+                    actor
+                        .outputs
+                        .iter()
+                        .flatten()
+                        .find(|output| output.get_hash() == input.get_hash())
+                        .map(|output| (i, output))
+                }) else {
+                    continue;
+                };
+                let uid = match input {
+                    IO::Bootstrap(data) | IO::Regular(data) | IO::Unbounded(data) => &data.uid,
+                };
+                edges.push(Edge {
+                    from,
+                    to,
+                    uid,
+                    bootstrap: matches!(output, IO::Bootstrap(_)),
+                    unbounded: matches!(input, IO::Unbounded(_)),
+                });
+            }
+        }
+        edges
+    }
+    /// Returns the network as a JSON document
+    ///
+    /// The document lists the actors with their inputs and outputs rates and the nested sub-systems they belong to,
+    /// and the edges from the output of an actor to the input of another actor,
+    /// the actors are referred to by their index in the list of actors:
+    /// ```json
+    /// {
+    ///   "actors": [
+    ///     {"id": 0, "name": "Signals", "inputs_rate": 0, "outputs_rate": 1, "subsystem": []},
+    ///     {"id": 1, "name": "Logging", "inputs_rate": 1, "outputs_rate": 0, "subsystem": ["Sink"]}
+    ///   ],
+    ///   "edges": [
+    ///     {"from": 0, "to": 1, "uid": "U", "bootstrap": false, "unbounded": false}
+    ///   ]
+    /// }
+    /// ```
+    pub fn as_json(&self) -> String {
+        let actors: Vec<_> = self
+            .actors
+            .iter()
+            .enumerate()
+            .map(|(i, actor)| {
+                format!(
+                    r#"    {{"id": {}, "name": {}, "inputs_rate": {}, "outputs_rate": {}, "subsystem": [{}]}}"#,
+                    i,
+                    json_string(&actor.client),
+                    actor.inputs_rate,
+                    actor.outputs_rate,
+                    actor
+                        .subsystem
+                        .iter()
+                        .map(|name| json_string(name))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+            .collect();
+        let edges: Vec<_> = self
+            .edges()
+            .into_iter()
+            .map(|edge| {
+                format!(
+                    r#"    {{"from": {}, "to": {}, "uid": {}, "bootstrap": {}, "unbounded": {}}}"#,
+                    edge.from,
+                    edge.to,
+                    json_string(edge.uid),
+                    edge.bootstrap,
+                    edge.unbounded
+                )
+            })
+            .collect();
+        format!(
+            "{{\n  \"actors\": [\n{}\n  ],\n  \"edges\": [\n{}\n  ]\n}}\n",
+            actors.join(",\n"),
+            edges.join(",\n")
+        )
+    }
+    /// Returns the diagram as a [Mermaid](https://mermaid.js.org/) flowchart
+    ///
+    /// The edges from bootstrapped outputs are drawn with thick lines,
+    /// the edges to unbounded inputs are drawn with dotted lines
+    /// and the actors of a sub-system are drawn inside a subgraph
+    pub fn as_mermaid(&self) -> String {
+        let mut mermaid = String::from("flowchart LR\n");
+        let actors: Vec<usize> = (0..self.actors.len())
+            .filter(|&i| self.actors[i].subsystem.is_empty())
+            .collect();
+        mermaid.push_str(&self.mermaid_nodes(&actors, &Cluster::tree(&self.actors), &mut 0, 2));
+        self.edges().into_iter().for_each(|edge| {
+            let arrow = match (edge.bootstrap, edge.unbounded) {
+                (true, _) => "==>",
+                (false, true) => "-.->",
+                (false, false) => "-->",
+            };
+            mermaid.push_str(&format!(
+                "  a{} {}|\"{}\"| a{}\n",
+                edge.from,
+                arrow,
+                mermaid_string(edge.uid),
+                edge.to
+            ))
+        });
+        mermaid
+    }
+    /// Returns the diagram as a SVG image
+    ///
+    /// The actors are laid out in layers from left to right following the flow of data
+    /// and the feedback edges are drawn below the actors.
+    /// As in the [Graphviz](https://www.graphviz.org/) diagram, the edges are colored according to the rates,
+    /// the edges from bootstrapped outputs are drawn in bold and the edges to unbounded inputs are dashed
+    pub fn as_svg(&self) -> String {
+        svg::render(&self.actors, &self.edges())
+    }
+    /// Writes the output of [Graph::as_svg()] to a file
+    pub fn to_svg<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let mut file = File::create(path)?;
+        write!(&mut file, "{}", self.as_svg())?;
+        Ok(())
+    }
+    /// Writes the output of [Graph::as_json()] to a file
+    pub fn to_json<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let mut file = File::create(path)?;
+        write!(&mut file, "{}", self.as_json())?;
+        Ok(())
+    }
+    /// Writes the output of [Graph::as_mermaid()] to a file
+    pub fn to_mermaid<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let mut file = File::create(path)?;
+        write!(&mut file, "{}", self.as_mermaid())?;
+        Ok(())
+    }
     /// Writes the output of [Graph::to_string()] to a file
     pub fn to_dot<P: AsRef<Path>>(
         &self,
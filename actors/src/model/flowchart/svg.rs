@@ -0,0 +1,582 @@
+//! Layered layout of the model network rendered to SVG
+//!
+//! The actors are assigned to layers from left to right following the flow of data.
+//! The feedback edges, i.e. the edges closing a loop, are ignored for the layering:
+//! they are routed through the gaps between the layers and below the actors.
+//! The edges spanning several layers go through virtual nodes in the intermediate layers
+//! and the order of the actors and virtual nodes within a layer is set with the barycenter heuristic,
+//! keeping together the actors of the same sub-system.
+//! The sub-systems are drawn as boxes around their actors.
+
+use super::{Cluster, Edge};
+use crate::actor::PlainActor;
+
+// Graphviz dark28 color scheme
+const COLORS: [&str; 8] = [
+    "#1b9e77", "#d95f02", "#7570b3", "#e7298a", "#66a61e", "#e6ab02", "#a6761d", "#666666",
+];
+const MARGIN: f64 = 40.;
+const NODE_HEIGHT: f64 = 40.;
+const IMAGE_HEIGHT: f64 = 80.;
+const LAYER_GAP: f64 = 120.;
+const NODE_GAP: f64 = 40.;
+const VIRTUAL_GAP: f64 = 20.;
+const FEEDBACK_GAP: f64 = 12.;
+const FONT_SIZE: f64 = 14.;
+const CLUSTER_PAD: f64 = 12.;
+const CLUSTER_LABEL: f64 = 16.;
+
+// Escapes a string for XML
+fn xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[derive(Default)]
+struct Node {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    // virtual nodes are waypoints of the edges spanning several layers
+    is_virtual: bool,
+    subsystem: Vec<String>,
+}
+impl Node {
+    fn middle(&self) -> f64 {
+        self.y + 0.5 * self.height
+    }
+    fn gap(&self) -> f64 {
+        if self.is_virtual {
+            VIRTUAL_GAP
+        } else {
+            NODE_GAP
+        }
+    }
+}
+
+// Vertical gap between 2 consecutive nodes of a layer,
+// leaving room for the boundaries of the sub-systems in between
+fn gap(upper: &Node, lower: &Node) -> f64 {
+    let common = upper
+        .subsystem
+        .iter()
+        .zip(&lower.subsystem)
+        .take_while(|(a, b)| a == b)
+        .count();
+    upper.gap()
+        + CLUSTER_PAD * (upper.subsystem.len() - common) as f64
+        + (CLUSTER_PAD + CLUSTER_LABEL) * (lower.subsystem.len() - common) as f64
+}
+
+// Boundaries (x0, y0, x1, y1) of the sub-system,
+// the boundaries of the nested sub-systems are appended to `rects` before the sub-system boundaries
+fn boundaries<'a>(
+    cluster: &Cluster<'a>,
+    nodes: &[Node],
+    rects: &mut Vec<(&'a str, [f64; 4])>,
+) -> [f64; 4] {
+    let mut rect = [
+        f64::INFINITY,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NEG_INFINITY,
+    ];
+    let mut enclose = |[x0, y0, x1, y1]: [f64; 4]| {
+        rect = [
+            rect[0].min(x0),
+            rect[1].min(y0),
+            rect[2].max(x1),
+            rect[3].max(y1),
+        ]
+    };
+    cluster.actors.iter().for_each(|&i| {
+        let node = &nodes[i];
+        enclose([node.x, node.y, node.x + node.width, node.y + node.height])
+    });
+    cluster
+        .clusters
+        .iter()
+        .for_each(|cluster| enclose(boundaries(cluster, nodes, rects)));
+    let rect = [
+        rect[0] - CLUSTER_PAD,
+        rect[1] - CLUSTER_PAD - CLUSTER_LABEL,
+        rect[2] + CLUSTER_PAD,
+        rect[3] + CLUSTER_PAD,
+    ];
+    rects.push((cluster.name, rect));
+    rect
+}
+
+// Returns true if `to` can be reached from `from` following the edges that are not flagged
+fn reaches(from: usize, to: usize, edges: &[Edge], flagged: &[bool]) -> bool {
+    let mut visited = vec![from];
+    let mut stack = vec![from];
+    while let Some(node) = stack.pop() {
+        if node == to {
+            return true;
+        }
+        for (edge, _) in edges
+            .iter()
+            .zip(flagged)
+            .filter(|(edge, &flagged)| !flagged && edge.from == node)
+        {
+            if !visited.contains(&edge.to) {
+                visited.push(edge.to);
+                stack.push(edge.to);
+            }
+        }
+    }
+    false
+}
+
+// Flags the edges that close a loop
+//
+// The loops are broken first at the back edges of a depth-first search over the regular edges,
+// then at the bootstrapped edges that still close a loop
+fn feedback_edges(n: usize, edges: &[Edge]) -> Vec<bool> {
+    let mut feedback: Vec<bool> = edges.iter().map(|edge| edge.bootstrap).collect();
+    // 0: not visited, 1: on the stack, 2: done
+    let mut state = vec![0u8; n];
+    for root in 0..n {
+        if state[root] != 0 {
+            continue;
+        }
+        let mut stack = vec![(root, 0usize)];
+        state[root] = 1;
+        while let Some((node, next)) = stack.pop() {
+            match edges
+                .iter()
+                .enumerate()
+                .skip(next)
+                .find(|(k, edge)| edge.from == node && !feedback[*k])
+            {
+                Some((k, edge)) => {
+                    stack.push((node, k + 1));
+                    match state[edge.to] {
+                        0 => {
+                            state[edge.to] = 1;
+                            stack.push((edge.to, 0));
+                        }
+                        1 => feedback[k] = true,
+                        _ => (),
+                    }
+                }
+                None => state[node] = 2,
+            }
+        }
+    }
+    for (k, edge) in edges.iter().enumerate() {
+        if edge.bootstrap {
+            feedback[k] = false;
+            feedback[k] = reaches(edge.to, edge.from, edges, &feedback);
+        }
+    }
+    feedback
+}
+
+// Longest path layering over the forward edges
+fn layering(n: usize, edges: &[Edge], feedback: &[bool]) -> Vec<usize> {
+    let mut layer = vec![0usize; n];
+    let mut in_degree = vec![0usize; n];
+    edges
+        .iter()
+        .zip(feedback)
+        .filter(|(_, &feedback)| !feedback)
+        .for_each(|(edge, _)| in_degree[edge.to] += 1);
+    let mut queue: Vec<usize> = (0..n).filter(|&i| in_degree[i] == 0).collect();
+    while let Some(node) = queue.pop() {
+        for (edge, _) in edges
+            .iter()
+            .zip(feedback)
+            .filter(|(edge, &feedback)| !feedback && edge.from == node)
+        {
+            layer[edge.to] = layer[edge.to].max(layer[node] + 1);
+            in_degree[edge.to] -= 1;
+            if in_degree[edge.to] == 0 {
+                queue.push(edge.to);
+            }
+        }
+    }
+    layer
+}
+
+// Orders the nodes within each layer with the barycenter of their neighbors in the previous
+// (downward sweeps) or next (upward sweeps) layer,
+// the nodes of a sub-system are ordered with the mean barycenter of the sub-system nodes within the layer
+fn ordering(
+    layer: &[usize],
+    segments: &[(usize, usize)],
+    subsystems: &[&[String]],
+) -> Vec<Vec<usize>> {
+    let n_layer = layer.iter().max().map_or(0, |l| l + 1);
+    let mut layers: Vec<Vec<usize>> = vec![vec![]; n_layer];
+    layer
+        .iter()
+        .enumerate()
+        .for_each(|(node, &l)| layers[l].push(node));
+    let mut rank = vec![0f64; layer.len()];
+    let update_rank = |layers: &[Vec<usize>], rank: &mut [f64]| {
+        layers.iter().for_each(|nodes| {
+            nodes
+                .iter()
+                .enumerate()
+                .for_each(|(i, &node)| rank[node] = i as f64)
+        })
+    };
+    update_rank(&layers, &mut rank);
+    for sweep in 0..8 {
+        let downward = sweep % 2 == 0;
+        let order: Vec<usize> = if downward {
+            (1..n_layer).collect()
+        } else {
+            (0..n_layer.saturating_sub(1)).rev().collect()
+        };
+        for l in order {
+            let barycenter = |node: usize| {
+                let neighbors: Vec<f64> = segments
+                    .iter()
+                    .filter_map(|&(from, to)| match (from == node, to == node) {
+                        (false, true) if downward => Some(rank[from]),
+                        (true, false) if !downward => Some(rank[to]),
+                        _ => None,
+                    })
+                    .collect();
+                if neighbors.is_empty() {
+                    rank[node]
+                } else {
+                    neighbors.iter().sum::<f64>() / neighbors.len() as f64
+                }
+            };
+            let nodes: Vec<(usize, f64)> = layers[l]
+                .iter()
+                .map(|&node| (node, barycenter(node)))
+                .collect();
+            let mut keys: Vec<(usize, Vec<f64>)> = nodes
+                .iter()
+                .map(|&(node, barycenter)| {
+                    let path = subsystems[node];
+                    let mut key: Vec<f64> = (1..=path.len())
+                        .map(|depth| {
+                            let members: Vec<f64> = nodes
+                                .iter()
+                                .filter(|(other, _)| subsystems[*other].starts_with(&path[..depth]))
+                                .map(|(_, barycenter)| *barycenter)
+                                .collect();
+                            members.iter().sum::<f64>() / members.len() as f64
+                        })
+                        .collect();
+                    key.push(barycenter);
+                    (node, key)
+                })
+                .collect();
+            keys.sort_by(|(_, a), (_, b)| {
+                a.iter()
+                    .zip(b)
+                    .map(|(a, b)| a.total_cmp(b))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or_else(|| a.len().cmp(&b.len()))
+            });
+            layers[l] = keys.into_iter().map(|(node, _)| node).collect();
+            update_rank(&layers, &mut rank);
+        }
+    }
+    layers
+}
+
+// Spreads the ends of the edges along the side of a node,
+// `ends` are the edges indices with the height of the node at the other end of the edge
+fn spread(node: &Node, mut ends: Vec<(usize, f64)>, y: &mut [f64]) {
+    ends.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let m = ends.len() as f64;
+    ends.into_iter()
+        .enumerate()
+        .for_each(|(i, (k, _))| y[k] = node.y + node.height * (i as f64 + 1.) / (m + 1.));
+}
+
+/// Renders the actors and their connections to SVG
+pub(super) fn render(actors: &[PlainActor], edges: &[Edge]) -> String {
+    let n = actors.len();
+    let clusters = Cluster::tree(actors);
+    let depth = clusters
+        .iter()
+        .map(|cluster| cluster.depth())
+        .max()
+        .unwrap_or_default() as f64;
+    let margin = MARGIN + depth * (CLUSTER_PAD + CLUSTER_LABEL);
+    let feedback = feedback_edges(n, edges);
+    let mut layer = layering(n, edges, &feedback);
+
+    let mut nodes: Vec<Node> = actors
+        .iter()
+        .map(|actor| Node {
+            width: (0.6 * FONT_SIZE * actor.client.chars().count() as f64 + 24.).max(120.),
+            height: NODE_HEIGHT + actor.image.as_ref().map_or(0., |_| IMAGE_HEIGHT),
+            subsystem: actor.subsystem.clone(),
+            ..Default::default()
+        })
+        .collect();
+    let layer_width: f64 = nodes.iter().map(|node| node.width).fold(0., f64::max);
+
+    // chains of nodes from the source to the target of the forward edges
+    let chains: Vec<Vec<usize>> = edges
+        .iter()
+        .zip(&feedback)
+        .map(|(edge, &feedback)| {
+            if feedback {
+                return vec![edge.from, edge.to];
+            }
+            let mut chain = vec![edge.from];
+            for l in layer[edge.from] + 1..layer[edge.to] {
+                chain.push(nodes.len());
+                layer.push(l);
+                nodes.push(Node {
+                    width: layer_width,
+                    is_virtual: true,
+                    ..Default::default()
+                });
+            }
+            chain.push(edge.to);
+            chain
+        })
+        .collect();
+    let segments: Vec<(usize, usize)> = chains
+        .iter()
+        .zip(&feedback)
+        .filter(|(_, &feedback)| !feedback)
+        .flat_map(|(chain, _)| chain.windows(2).map(|w| (w[0], w[1])))
+        .collect();
+    let subsystems: Vec<&[String]> = nodes.iter().map(|node| node.subsystem.as_slice()).collect();
+    let layers = ordering(&layer, &segments, &subsystems);
+
+    // nodes geometry
+    let layer_height = |nodes: &[Node], layer: &[usize]| -> f64 {
+        layer.iter().map(|&i| nodes[i].height).sum::<f64>()
+            + layer
+                .windows(2)
+                .map(|w| gap(&nodes[w[0]], &nodes[w[1]]))
+                .sum::<f64>()
+    };
+    let height = layers
+        .iter()
+        .map(|layer| layer_height(&nodes, layer))
+        .fold(0., f64::max);
+    for (l, layer) in layers.iter().enumerate() {
+        let mut y = margin + 0.5 * (height - layer_height(&nodes, layer));
+        for (k, &i) in layer.iter().enumerate() {
+            let next = layer.get(k + 1).map_or(0., |&j| gap(&nodes[i], &nodes[j]));
+            let node = &mut nodes[i];
+            node.x =
+                margin + l as f64 * (layer_width + LAYER_GAP) + 0.5 * (layer_width - node.width);
+            node.y = y;
+            y += node.height + next;
+        }
+    }
+
+    // edges ends along the right (sources) and left (targets) sides of the actors,
+    // the feedback edges are attached below the forward edges
+    let mut source_y = vec![0f64; edges.len()];
+    let mut target_y = vec![0f64; edges.len()];
+    for i in 0..n {
+        let other = |k: usize, end: usize| {
+            if feedback[k] {
+                f64::INFINITY
+            } else {
+                nodes[end].middle()
+            }
+        };
+        let outgoing: Vec<_> = chains
+            .iter()
+            .enumerate()
+            .filter(|(_, chain)| chain[0] == i)
+            .map(|(k, chain)| (k, other(k, chain[1])))
+            .collect();
+        spread(&nodes[i], outgoing, &mut source_y);
+        let incoming: Vec<_> = chains
+            .iter()
+            .enumerate()
+            .filter(|(_, chain)| chain[chain.len() - 1] == i)
+            .map(|(k, chain)| (k, other(k, chain[chain.len() - 2])))
+            .collect();
+        spread(&nodes[i], incoming, &mut target_y);
+    }
+
+    // edges colors by rate
+    let mut lookup: Vec<usize> = vec![];
+    let color: Vec<usize> = edges
+        .iter()
+        .map(|edge| {
+            let rate = actors[edge.from].outputs_rate;
+            lookup.iter().position(|&r| r == rate).unwrap_or_else(|| {
+                lookup.push(rate);
+                lookup.len() - 1
+            }) % COLORS.len()
+        })
+        .collect();
+
+    let mut paths = vec![];
+    let mut labels = vec![];
+    let mut n_feedback = 0;
+    let bezier = |(x1, y1): (f64, f64), (x2, y2): (f64, f64)| {
+        let dx = 0.5 * (x2 - x1);
+        format!(
+            " C {:.1} {y1:.1}, {:.1} {y2:.1}, {x2:.1} {y2:.1}",
+            x1 + dx,
+            x2 - dx
+        )
+    };
+    for (k, (edge, chain)) in edges.iter().zip(&chains).enumerate() {
+        let (from, to) = (&nodes[edge.from], &nodes[edge.to]);
+        let start = (from.x + from.width, source_y[k]);
+        let end = (to.x, target_y[k]);
+        let (path, label_at) = if feedback[k] {
+            // feedback edges go down in the gap next to the source, below the actors
+            // and up in the gap next to the target
+            n_feedback += 1;
+            let offset = (FEEDBACK_GAP * n_feedback as f64).min(0.5 * LAYER_GAP - 10.);
+            let y = margin + height + depth * CLUSTER_PAD + FEEDBACK_GAP * n_feedback as f64;
+            let (x1, x2) = (start.0 + offset, end.0 - offset);
+            (
+                format!(
+                    "M {:.1} {:.1} L {x1:.1} {:.1} L {x1:.1} {y:.1} L {x2:.1} {y:.1} L {x2:.1} {:.1} L {:.1} {:.1}",
+                    start.0, start.1, start.1, end.1, end.0, end.1
+                ),
+                (0.5 * (x1 + x2), y - 3.),
+            )
+        } else {
+            let mut path = format!("M {:.1} {:.1}", start.0, start.1);
+            let mut point = start;
+            for &v in &chain[1..chain.len() - 1] {
+                let node = &nodes[v];
+                path.push_str(&bezier(point, (node.x, node.y)));
+                path.push_str(&format!(" L {:.1} {:.1}", node.x + node.width, node.y));
+                point = (node.x + node.width, node.y);
+            }
+            path.push_str(&bezier(point, end));
+            (
+                path,
+                (end.0 - 0.25 * LAYER_GAP, 0.25 * point.1 + 0.75 * end.1 - 4.),
+            )
+        };
+        let style = match (edge.bootstrap, edge.unbounded) {
+            (true, _) => r#" stroke-width="3""#,
+            (false, true) => r#" stroke-width="1.5" stroke-dasharray="6 4""#,
+            (false, false) => r#" stroke-width="1.5""#,
+        };
+        paths.push(format!(
+            r#"  <path d="{path}" fill="none" stroke="{0}"{style} marker-end="url(#arrow{1})"/>"#,
+            COLORS[color[k]], color[k]
+        ));
+        labels.push(format!(
+            r#"  <text x="{:.1}" y="{:.1}" font-size="10" fill="lightgray" text-anchor="middle">{}</text>"#,
+            label_at.0,
+            label_at.1,
+            xml(edge.uid)
+        ));
+    }
+
+    // sub-systems boundaries, the enclosing sub-systems first
+    let mut rects = vec![];
+    clusters.iter().for_each(|cluster| {
+        boundaries(cluster, &nodes, &mut rects);
+    });
+    rects.reverse();
+    let rects: Vec<String> = rects
+        .into_iter()
+        .map(|(name, [x0, y0, x1, y1])| {
+            format!(
+                r#"  <rect x="{x0:.1}" y="{y0:.1}" width="{:.1}" height="{:.1}" rx="12" fill="white" fill-opacity="0.05" stroke="lightgray" stroke-dasharray="4 3"/>
+  <text x="{:.1}" y="{:.1}" font-size="12" fill="lightgray">{}</text>"#,
+                x1 - x0,
+                y1 - y0,
+                x0 + 8.,
+                y0 + CLUSTER_LABEL - 2.,
+                xml(name)
+            )
+        })
+        .collect();
+
+    let boxes: Vec<String> = actors
+        .iter()
+        .zip(&nodes)
+        .map(|(actor, node)| {
+            let mut svg = format!(
+                r#"  <rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" rx="8" fill="lightgray"/>"#,
+                node.x, node.y, node.width, node.height
+            );
+            if let Some(image) = actor.image.as_ref() {
+                svg.push_str(&format!(
+                    r#"
+  <image href="{}" x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" preserveAspectRatio="xMidYMid meet"/>"#,
+                    xml(image),
+                    node.x + 4.,
+                    node.y + NODE_HEIGHT - 8.,
+                    node.width - 8.,
+                    IMAGE_HEIGHT
+                ));
+            }
+            svg.push_str(&format!(
+                r#"
+  <text x="{:.1}" y="{:.1}" font-size="{FONT_SIZE}" fill="black" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                node.x + 0.5 * node.width,
+                node.y + 0.5 * NODE_HEIGHT,
+                xml(&actor.client)
+            ));
+            svg
+        })
+        .collect();
+
+    let markers: Vec<String> = COLORS
+        .iter()
+        .enumerate()
+        .map(|(i, color)| {
+            format!(
+                r#"    <marker id="arrow{i}" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="8" markerHeight="8" orient="auto"><path d="M 0 0 L 10 5 L 0 10 z" fill="{color}"/></marker>"#
+            )
+        })
+        .collect();
+
+    // rates legend
+    let legend_y = margin + height + depth * CLUSTER_PAD + FEEDBACK_GAP * n_feedback as f64 + 30.;
+    let legend: Vec<String> = lookup
+        .iter()
+        .enumerate()
+        .map(|(i, rate)| {
+            let x = margin + 90. * i as f64;
+            format!(
+                r#"  <line x1="{x:.1}" y1="{legend_y:.1}" x2="{:.1}" y2="{legend_y:.1}" stroke="{}" stroke-width="3"/>
+  <text x="{:.1}" y="{legend_y:.1}" font-size="10" fill="lightgray" dominant-baseline="central">rate {rate}</text>"#,
+                x + 20.,
+                COLORS[i % COLORS.len()],
+                x + 26.
+            )
+        })
+        .collect();
+
+    let width = 2. * margin + layers.len() as f64 * (layer_width + LAYER_GAP) - LAYER_GAP;
+    let height = legend_y + MARGIN;
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width:.0}" height="{height:.0}" viewBox="0 0 {width:.0} {height:.0}" font-family="sans-serif">
+  <defs>
+{}
+  </defs>
+  <rect width="100%" height="100%" fill="#3d3d3d"/>
+{}
+{}
+{}
+{}
+</svg>
+"##,
+        markers.join("\n"),
+        rects
+            .into_iter()
+            .chain(paths)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        boxes.join("\n"),
+        labels.join("\n"),
+        legend.join("\n")
+    )
+}
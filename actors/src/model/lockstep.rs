@@ -0,0 +1,176 @@
+//! Deterministic single-threaded lockstep scheduler
+//!
+//! The actor tasks are polled one after the other on the calling thread,
+//! following a topological order of the actor network.
+//! An actor is polled again only after one of its channels has woken it up,
+//! so the same model always goes through the same sequence of polls.
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+};
+
+use super::{ActorReport, Actors, ModelError, ModelReport, Result};
+use crate::actor::{plain::IO, PlainActor};
+
+/// Record of the actor polls performed by the lockstep scheduler
+///
+/// Two runs of the same model yield the same trace
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Trace {
+    actors: Vec<String>,
+    polls: Vec<usize>,
+}
+impl Trace {
+    /// Returns the names of the actors in scheduling order
+    pub fn actors(&self) -> &[String] {
+        &self.actors
+    }
+    /// Returns the sequence of polls as indices into [Trace::actors]
+    pub fn polls(&self) -> &[usize] {
+        &self.polls
+    }
+    /// Returns the number of polls
+    pub fn len(&self) -> usize {
+        self.polls.len()
+    }
+    /// Checks if the trace is empty
+    pub fn is_empty(&self) -> bool {
+        self.polls.is_empty()
+    }
+}
+impl Display for Trace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (k, &i) in self.polls.iter().enumerate() {
+            writeln!(f, "{:>6}: {}", k, self.actors[i])?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the actors indices sorted in topological order
+///
+/// The edges of bootstrapped outputs are ignored, breaking the feedback loops.
+/// Ties are resolved with the order the actors have been added to the model.
+pub(crate) fn topological_order(actors: &[PlainActor]) -> Vec<usize> {
+    let mut producers: HashMap<u64, usize> = HashMap::new();
+    for (i, actor) in actors.iter().enumerate() {
+        for output in actor.outputs.iter().flatten() {
+            if let IO::Regular(data) | IO::Unbounded(data) = output {
+                producers.insert(data.hash, i);
+            }
+        }
+    }
+    let mut downstream: Vec<Vec<usize>> = vec![vec![]; actors.len()];
+    let mut in_degree = vec![0usize; actors.len()];
+    for (i, actor) in actors.iter().enumerate() {
+        for input in actor.inputs.iter().flatten() {
+            if let Some(&j) = producers.get(&input.get_hash()) {
+                downstream[j].push(i);
+                in_degree[i] += 1;
+            }
+        }
+    }
+    let mut order = Vec::with_capacity(actors.len());
+    let mut visited = vec![false; actors.len()];
+    while order.len() < actors.len() {
+        let next = (0..actors.len())
+            .find(|&i| !visited[i] && in_degree[i] == 0)
+            // remaining actors are in a loop without bootstrap
+            .or_else(|| (0..actors.len()).find(|&i| !visited[i]))
+            .unwrap();
+        visited[next] = true;
+        order.push(next);
+        for &i in &downstream[next] {
+            in_degree[i] = in_degree[i].saturating_sub(1);
+        }
+    }
+    order
+}
+
+struct Flag(AtomicBool);
+impl Wake for Flag {
+    fn wake(self: Arc<Self>) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Runs the actors tasks to completion in lockstep
+///
+/// The tasks are polled from a dedicated thread outside of any tokio runtime,
+/// otherwise the tokio resources would share the cooperative budget of the calling task.
+/// Each actor is dropped as soon as its task is completed,
+/// closing its channels as it would when spawned on the tokio runtime.
+pub(super) fn run(actors: Actors) -> Result<(Trace, ModelReport)> {
+    std::thread::spawn(move || step(actors))
+        .join()
+        .map_err(|_| ModelError::LockstepPanic)?
+}
+
+fn step(actors: Actors) -> Result<(Trace, ModelReport)> {
+    let plain: Vec<_> = actors.iter().map(|actor| actor.as_plain()).collect();
+    let order = topological_order(&plain);
+    let names: Vec<_> = order.iter().map(|&i| plain[i].client.clone()).collect();
+
+    let mut tasks: Vec<Option<Pin<Box<dyn Future<Output = ActorReport> + Send>>>> = actors
+        .into_iter()
+        .map(|mut actor| {
+            let task: Pin<Box<dyn Future<Output = ActorReport> + Send>> =
+                Box::pin(async move { actor.task().await });
+            Some(task)
+        })
+        .collect();
+    let mut reports: Vec<Option<ActorReport>> = vec![None; tasks.len()];
+    let flags: Vec<_> = (0..tasks.len())
+        .map(|_| Arc::new(Flag(AtomicBool::new(true))))
+        .collect();
+    let wakers: Vec<Waker> = flags.iter().cloned().map(Waker::from).collect();
+
+    let mut polls = vec![];
+    let mut n_pending = tasks.len();
+    while n_pending > 0 {
+        let mut progress = false;
+        for (k, &i) in order.iter().enumerate() {
+            if !flags[i].0.swap(false, Ordering::SeqCst) {
+                continue;
+            }
+            let Some(task) = tasks[i].as_mut() else {
+                continue;
+            };
+            progress = true;
+            polls.push(k);
+            let mut cx = Context::from_waker(&wakers[i]);
+            if let Poll::Ready(report) = task.as_mut().poll(&mut cx) {
+                reports[i] = Some(report);
+                tasks[i] = None;
+                n_pending -= 1;
+            }
+        }
+        if !progress {
+            let pending = order
+                .iter()
+                .enumerate()
+                .filter(|(_, &i)| tasks[i].is_some())
+                .map(|(k, _)| names[k].clone())
+                .collect();
+            return Err(ModelError::Deadlock(pending));
+        }
+    }
+    Ok((
+        Trace {
+            actors: names,
+            polls,
+        },
+        ModelReport::new(reports.into_iter().flatten().collect()),
+    ))
+}
@@ -0,0 +1,95 @@
+use std::{fmt::Display, time::Duration};
+
+use super::ModelReport;
+
+/// Timing statistics of a phase of the actor loop
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timing {
+    /// Number of times the phase has been executed
+    pub n: usize,
+    /// Mean duration of the phase
+    pub mean: Duration,
+    /// Maximum duration of the phase
+    pub max: Duration,
+}
+
+/// Timing of the actor loop phases
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Profile {
+    /// Time spent waiting on the inputs
+    pub inputs: Timing,
+    /// Time spent in the client [update](crate::Update::update)
+    pub update: Timing,
+    /// Time spent blocked on the outputs
+    pub outputs: Timing,
+}
+
+/// Timing summary of a [Model](super::Model) with one row per actor
+///
+/// The summary is available only if the model was profiled
+#[derive(Debug, Clone, Default)]
+pub struct ProfileSummary(Vec<(String, usize, Profile)>);
+impl ProfileSummary {
+    /// Returns the summary of a model report, if the actors have been profiled
+    pub fn new(report: &ModelReport) -> Option<Self> {
+        let rows: Vec<_> = report
+            .iter()
+            .filter_map(|report| {
+                report
+                    .profile
+                    .map(|profile| (report.name.clone(), report.n_step, profile))
+            })
+            .collect();
+        (!rows.is_empty()).then_some(Self(rows))
+    }
+    /// Returns an iterator over the actors name, # of steps and [Profile]
+    pub fn iter(&self) -> impl Iterator<Item = &(String, usize, Profile)> {
+        self.0.iter()
+    }
+    /// Returns the [Profile] of the actor with the given name
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        self.0
+            .iter()
+            .find(|(actor, ..)| actor == name)
+            .map(|(_, _, profile)| profile)
+    }
+}
+impl Display for ProfileSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self
+            .0
+            .iter()
+            .map(|(name, ..)| name.len())
+            .max()
+            .unwrap_or_default()
+            .max(5);
+        let us = |d: Duration| d.as_secs_f64() * 1e6;
+        writeln!(
+            f,
+            "{:<width$} {:>10} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12}",
+            "ACTOR",
+            "STEPS",
+            "INPUTS MEAN",
+            "INPUTS MAX",
+            "UPDATE MEAN",
+            "UPDATE MAX",
+            "OUTPUTS MEAN",
+            "OUTPUTS MAX"
+        )?;
+        for (name, n_step, p) in &self.0 {
+            writeln!(
+                f,
+                "{:<width$} {:>10} {:>12.1} {:>12.1} {:>12.1} {:>12.1} {:>12.1} {:>12.1}",
+                name,
+                n_step,
+                us(p.inputs.mean),
+                us(p.inputs.max),
+                us(p.update.mean),
+                us(p.update.max),
+                us(p.outputs.mean),
+                us(p.outputs.max)
+            )?;
+        }
+        write!(f, "(times in micro-seconds)")
+    }
+}
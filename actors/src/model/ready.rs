@@ -1,9 +1,13 @@
-use super::{Model, Ready, Running};
+use super::{
+    lockstep, Completed, Model, ModelHandle, ProfileSummary, Ready, Result, Running, TaskHandle,
+};
 use chrono::{DateTime, Local, SecondsFormat};
 use std::{marker::PhantomData, time::Instant};
 
 impl Model<Ready> {
     /// Spawns each actor task
+    ///
+    /// The running model can be controlled with the [ModelHandle] given by [Model::handle]
     pub fn run(mut self) -> Model<Running> {
         let now: DateTime<Local> = Local::now();
         println!(
@@ -14,19 +18,86 @@ impl Model<Ready> {
                 .to_uppercase(),
             now.to_rfc3339_opts(SecondsFormat::Secs, true),
         );
+        let (handle, control) = ModelHandle::new();
         let mut actors = self.actors.take().unwrap();
         let mut task_handles = vec![];
         while let Some(mut actor) = actors.pop() {
-            task_handles.push(tokio::spawn(async move {
-                actor.task().await;
-            }));
+            actor.set_control(control.clone());
+            task_handles.push(TaskHandle {
+                name: actor.name(),
+                monitor: actor.monitor(),
+                #[cfg(feature = "checkpoint")]
+                inputs_hashes: actor.inputs_hashes(),
+                #[cfg(feature = "checkpoint")]
+                client_state: actor.client_state(),
+                #[cfg(feature = "checkpoint")]
+                input_queues: actor.input_queues(),
+                join_handle: tokio::spawn(async move { actor.task().await }),
+            });
         }
+        let handle = handle.abort_handles(
+            task_handles
+                .iter()
+                .map(|task_handle| task_handle.join_handle.abort_handle())
+                .collect(),
+        );
         Model::<Running> {
             name: self.name,
             actors: None,
             task_handles: Some(task_handles),
             state: PhantomData,
             start: Instant::now(),
+            trace: None,
+            handle: Some(handle),
+            report: None,
+            watchdog: None,
         }
     }
+    /// Runs the actors tasks to completion on the current thread
+    ///
+    /// The actors are stepped one after the other in the topological order of the model network
+    /// (with the bootstrapped outputs breaking the feedback loops),
+    /// an actor being stepped again only after one of its channels is ready.
+    /// The sequence of steps is then the same from one run to the next and is saved in
+    /// the [Trace](super::Trace) of the completed model.
+    ///
+    /// An error is returned if all the remaining actors are waiting on each other
+    /// or if the client of an actor has panicked.
+    pub fn run_lockstep(mut self) -> Result<Model<Completed>> {
+        let now: DateTime<Local> = Local::now();
+        let name = self
+            .name
+            .as_ref()
+            .unwrap_or(&String::from("Model"))
+            .to_uppercase();
+        println!(
+            "[{}<{}>] LAUNCHED (lockstep)",
+            name,
+            now.to_rfc3339_opts(SecondsFormat::Secs, true),
+        );
+        let start = Instant::now();
+        let (trace, report) = lockstep::run(self.actors.take().unwrap())?;
+        report.check_panics()?;
+        let now: DateTime<Local> = Local::now();
+        println!(
+            "[{}<{}>] COMPLETED in {}",
+            name,
+            now.to_rfc3339_opts(SecondsFormat::Secs, true),
+            humantime::format_duration(Instant::now().duration_since(start))
+        );
+        if let Some(summary) = ProfileSummary::new(&report) {
+            println!("{}", summary);
+        }
+        Ok(Model::<Completed> {
+            name: self.name,
+            actors: None,
+            task_handles: None,
+            state: PhantomData,
+            start: Instant::now(),
+            trace: Some(trace),
+            handle: None,
+            report: Some(report),
+            watchdog: None,
+        })
+    }
 }
@@ -0,0 +1,213 @@
+use super::{ModelError, Profile, Result};
+use crate::{actor::Monitor, ActorError};
+use futures::FutureExt;
+use std::{any::Any, fmt::Display, future::Future, panic::AssertUnwindSafe};
+
+/// Reason for an actor to exit its loop
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExitReason {
+    /// A client has no more data to write
+    NoData,
+    /// The model has been stopped with the [ModelHandle](super::ModelHandle)
+    Stopped,
+    /// An output channel has been closed by the receiving actor
+    DropSend,
+    /// An input channel has been closed by the sending actor
+    DropRecv,
+    /// The actor task has been aborted with the [ModelHandle](super::ModelHandle)
+    Aborted,
+    /// The actor client has panicked, with the panic message
+    Panicked(String),
+    /// Any other error
+    Other(String),
+}
+impl ExitReason {
+    /// Checks if the reason is one of the expected ways for an actor to exit
+    ///
+    /// An actor exits normally when its client runs out of data, when the model is stopped
+    /// or when the actors it is connected to have themselves exited.
+    pub fn is_normal(&self) -> bool {
+        matches!(
+            self,
+            ExitReason::NoData | ExitReason::Stopped | ExitReason::DropSend | ExitReason::DropRecv
+        )
+    }
+}
+impl Display for ExitReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExitReason::NoData => write!(f, "no data"),
+            ExitReason::Stopped => write!(f, "stopped"),
+            ExitReason::DropSend => write!(f, "receiver dropped"),
+            ExitReason::DropRecv => write!(f, "sender dropped"),
+            ExitReason::Aborted => write!(f, "aborted"),
+            ExitReason::Panicked(msg) => write!(f, "panicked: {msg}"),
+            ExitReason::Other(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+/// Actor termination report
+#[derive(Debug, Clone)]
+pub struct ActorReport {
+    /// Actor name
+    pub name: String,
+    /// Reason the actor exited
+    pub exit: ExitReason,
+    /// Number of client updates
+    pub n_step: usize,
+    /// Channel that ended the actor loop
+    pub channel: Option<String>,
+    /// Number of messages dropped by the outputs overflow policies
+    pub n_dropped: usize,
+    /// Timing of the actor loop, if the model was profiled
+    pub profile: Option<Profile>,
+}
+impl ActorReport {
+    pub(crate) fn new(name: String, n_step: usize, result: crate::Result<()>) -> Self {
+        let (exit, channel) = match result {
+            Ok(_) | Err(ActorError::NoData) => (ExitReason::NoData, None),
+            Err(ActorError::Disconnected(output)) => (ExitReason::NoData, Some(output)),
+            Err(ActorError::Stopped) => (ExitReason::Stopped, None),
+            Err(ActorError::DropSend { msg, .. }) => (ExitReason::DropSend, Some(msg)),
+            Err(ActorError::DropRecv { msg, .. }) => (ExitReason::DropRecv, Some(msg)),
+            Err(e) => (ExitReason::Other(e.to_string()), None),
+        };
+        Self {
+            name,
+            exit,
+            n_step,
+            channel,
+            n_dropped: 0,
+            profile: None,
+        }
+    }
+    pub(crate) fn abnormal(name: String, n_step: usize, exit: ExitReason) -> Self {
+        Self {
+            name,
+            exit,
+            n_step,
+            channel: None,
+            n_dropped: 0,
+            profile: None,
+        }
+    }
+    /// Creates the report of an actor that has panicked at step `n_step`
+    pub(crate) fn panicked(name: String, n_step: usize, payload: Box<dyn Any + Send>) -> Self {
+        let msg = payload
+            .downcast_ref::<&str>()
+            .map(|msg| msg.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| String::from("unknown panic payload"));
+        log::error!("{name} panicked at step {n_step}: {msg}");
+        Self::abnormal(name, n_step, ExitReason::Panicked(msg))
+    }
+    /// Runs the task of an actor, catching a panic of the task into the report of the actor
+    ///
+    /// The actor is then dropped as if it had exited normally, closing its channels
+    pub(crate) async fn catch<F>(name: String, monitor: &Monitor, task: F) -> Self
+    where
+        F: Future<Output = Self>,
+    {
+        let report = AssertUnwindSafe(task).catch_unwind().await;
+        monitor.exit();
+        match report {
+            Ok(report) => report,
+            Err(payload) => {
+                let mut report = Self::panicked(name, monitor.n_step(), payload);
+                report.profile = monitor.profile();
+                report.n_dropped = monitor.n_dropped();
+                report
+            }
+        }
+    }
+}
+impl Display for ActorReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} after {} steps", self.name, self.exit, self.n_step)?;
+        if let Some(channel) = &self.channel {
+            write!(f, " ({channel})")?;
+        }
+        if self.n_dropped > 0 {
+            write!(f, ", {} messages dropped", self.n_dropped)?;
+        }
+        Ok(())
+    }
+}
+
+/// [Model](super::Model) termination report
+///
+/// The report is a collection of [ActorReport], one for each actor of the model
+#[derive(Debug, Clone, Default)]
+pub struct ModelReport(Vec<ActorReport>);
+impl ModelReport {
+    pub(crate) fn new(reports: Vec<ActorReport>) -> Self {
+        Self(reports)
+    }
+    /// Returns an iterator over the actors reports
+    pub fn iter(&self) -> impl Iterator<Item = &ActorReport> {
+        self.0.iter()
+    }
+    /// Returns the report of the actor with the given name
+    pub fn get(&self, name: &str) -> Option<&ActorReport> {
+        self.0.iter().find(|report| report.name == name)
+    }
+    /// Returns the reports of the actors that exited abnormally
+    ///
+    /// If none of the actors ran out of data or was stopped,
+    /// the model was brought down by a channel failure and all the actors are deemed abnormal
+    pub fn abnormal(&self) -> Vec<&ActorReport> {
+        let ended = self
+            .0
+            .iter()
+            .any(|report| matches!(report.exit, ExitReason::NoData | ExitReason::Stopped));
+        self.0
+            .iter()
+            .filter(|report| !(ended && report.exit.is_normal()))
+            .collect()
+    }
+    /// Checks if all the actors exited normally
+    pub fn is_normal(&self) -> bool {
+        self.abnormal().is_empty()
+    }
+    /// Returns an error with the first actor that has panicked, if any
+    pub fn check_panics(&self) -> Result<()> {
+        match self.0.iter().find_map(|report| match &report.exit {
+            ExitReason::Panicked(msg) => Some((report, msg)),
+            _ => None,
+        }) {
+            Some((report, msg)) => Err(ModelError::ActorPanicked {
+                actor: report.name.clone(),
+                step: report.n_step,
+                msg: msg.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+    /// Returns an error listing the actors that exited abnormally, if any
+    pub fn check(&self) -> Result<()> {
+        let abnormal = self.abnormal();
+        if abnormal.is_empty() {
+            Ok(())
+        } else {
+            Err(ModelError::AbnormalExit(
+                abnormal.into_iter().map(|report| report.to_string()).collect(),
+            ))
+        }
+    }
+}
+impl Display for ModelReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for report in &self.0 {
+            writeln!(f, " {report}")?;
+        }
+        Ok(())
+    }
+}
+impl<'a> IntoIterator for &'a ModelReport {
+    type Item = &'a ActorReport;
+    type IntoIter = std::slice::Iter<'a, ActorReport>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
@@ -1,18 +1,107 @@
-use super::{Completed, Model, ModelError, Result, Running};
+use super::{
+    watchdog::Watchdog, ActorReport, Completed, ExitReason, Model, ModelError, ModelHandle,
+    ModelReport, ProfileSummary, Result, Running,
+};
 use chrono::{DateTime, Local, SecondsFormat};
 use std::{
     future::{Future, IntoFuture},
     marker::PhantomData,
     pin::Pin,
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 impl Model<Running> {
+    /// Returns a handle to stop, pause, resume or abort the model
+    pub fn handle(&self) -> ModelHandle {
+        self.handle
+            .as_ref()
+            .cloned()
+            .expect("a running model has a control handle")
+    }
+    /// Aborts the model if none of the actors updates its client within `timeout`
+    ///
+    /// Before aborting, the channels each actor is waiting on, with their fill levels, are logged
+    /// and [Model::wait] returns [ModelError::Stalled] with the same information.
+    /// The watchdog is idle while some actors are paused.
+    pub fn watchdog(mut self, timeout: Duration) -> Self {
+        let monitors = self
+            .task_handles
+            .iter()
+            .flatten()
+            .map(|task_handle| (task_handle.name.clone(), task_handle.monitor.clone()))
+            .collect();
+        self.watchdog = Some(Watchdog::spawn(monitors, self.handle(), timeout));
+        self
+    }
     /// Waits for the task of each actor to finish
+    ///
+    /// The completed model holds the [ModelReport] on how each actor exited.
+    /// If the client of an actor has panicked, [ModelError::ActorPanicked] is returned
+    /// with the name of the actor, the step and the panic message.
+    /// ```
+    /// # tokio_test::block_on(async {
+    /// use gmt_dos_actors::{model::ModelError, prelude::*};
+    /// use gmt_dos_clients::{
+    ///     interface::{Data, Update, Write, UID},
+    ///     Logging,
+    /// };
+    /// #[derive(UID)]
+    /// enum Count {}
+    /// struct Counter(usize);
+    /// impl Update for Counter {
+    ///     fn update(&mut self) {
+    ///         assert!(self.0 < 4, "counter overflow");
+    ///         self.0 += 1;
+    ///     }
+    /// }
+    /// impl Write<Count> for Counter {
+    ///     fn write(&mut self) -> Option<Data<Count>> {
+    ///         Some(vec![self.0 as f64].into())
+    ///     }
+    /// }
+    /// let mut counter: Initiator<_> = (Counter(0), "Counter").into();
+    /// let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+    /// counter.add_output().build::<Count>().into_input(&mut sink)?;
+    ///
+    /// let Err(ModelError::ActorPanicked { actor, step, msg }) =
+    ///     model!(counter, sink).check()?.run().await
+    /// else {
+    ///     panic!("the counter should have panicked")
+    /// };
+    /// assert_eq!((actor.as_str(), step, msg.as_str()), ("Counter", 4, "counter overflow"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
     pub async fn wait(mut self) -> Result<Model<Completed>> {
         let task_handles = self.task_handles.take().unwrap();
+        let mut reports = vec![];
         for task_handle in task_handles.into_iter() {
-            task_handle.await?;
+            match task_handle.join_handle.await {
+                Ok(report) => reports.push(report),
+                // tasks cancelled with `ModelHandle::abort`
+                Err(e) if e.is_cancelled() => {
+                    let mut report = ActorReport::abnormal(
+                        task_handle.name,
+                        task_handle.monitor.n_step(),
+                        ExitReason::Aborted,
+                    );
+                    report.profile = task_handle.monitor.profile();
+                    reports.push(report)
+                }
+                // tasks that do not catch the panics of their clients
+                Err(e) if e.is_panic() => reports.push(ActorReport::panicked(
+                    task_handle.name,
+                    task_handle.monitor.n_step(),
+                    e.into_panic(),
+                )),
+                Err(e) => return Err(e.into()),
+            }
+        }
+        let stalled = self.watchdog.take().and_then(Watchdog::stop);
+        let report = ModelReport::new(reports);
+        report.check_panics()?;
+        if let Some(dump) = stalled {
+            return Err(ModelError::Stalled(dump));
         }
         let elapsed_time = Instant::now().duration_since(self.start);
         let now: DateTime<Local> = Local::now();
@@ -25,12 +114,19 @@ impl Model<Running> {
             now.to_rfc3339_opts(SecondsFormat::Secs, true),
             humantime::format_duration(elapsed_time)
         );
+        if let Some(summary) = ProfileSummary::new(&report) {
+            println!("{}", summary);
+        }
         Ok(Model::<Completed> {
             name: self.name,
             actors: None,
             task_handles: None,
             state: PhantomData,
             start: Instant::now(),
+            trace: None,
+            handle: None,
+            report: Some(report),
+            watchdog: None,
         })
     }
 }
@@ -1,6 +1,6 @@
 use crate::{model, Actor, Update};
 
-use super::{Actors, Model, ModelError, Ready, Result, Unknown};
+use super::{validation, Actors, Model, ModelError, Ready, Result, Unknown};
 use std::{
     marker::PhantomData,
     ops::{Add, AddAssign},
@@ -15,19 +15,29 @@ impl Default for Model<Unknown> {
             task_handles: Default::default(),
             state: Default::default(),
             start: Instant::now(),
+            trace: None,
+            handle: None,
+            report: None,
+            watchdog: None,
         }
     }
 }
 
 impl Model<Unknown> {
     /// Returns a new model
+    ///
+    /// The [sub-systems](crate::subsystem::SubSystem) are replaced by their actors
     pub fn new(actors: Actors) -> Self {
         Self {
             name: None,
-            actors: Some(actors),
+            actors: Some(crate::subsystem::flatten(actors)),
             task_handles: None,
             state: PhantomData,
             start: Instant::now(),
+            trace: None,
+            handle: None,
+            report: None,
+            watchdog: None,
         }
     }
     /// Sets the model name
@@ -37,38 +47,67 @@ impl Model<Unknown> {
             ..self
         }
     }
+    /// Times the phases of the actors loop
+    ///
+    /// Once the model is completed, the time spent by each actor waiting on its inputs,
+    /// updating its client and blocked on its outputs is given by `profile` on the completed model
+    pub fn profile(self) -> Self {
+        self.actors
+            .iter()
+            .flatten()
+            .for_each(|actor| actor.monitor().enable_profiling());
+        self
+    }
+    /// Sets the sampling frequency of the model in Hz
+    ///
+    /// The [Stamp](crate::interface::Stamp) of the data sent by the actors
+    /// then gives the simulation time in addition to the simulation step
+    /// ```
+    /// # tokio_test::block_on(async {
+    /// use gmt_dos_actors::prelude::*;
+    /// use gmt_dos_clients::{interface::UID, Logging, Signals};
+    /// #[derive(UID)]
+    /// enum Sig {};
+    /// let mut source: Initiator<_, 10> = Signals::new(1, 10).into();
+    /// let logging = Logging::<f64>::default().into_arcx();
+    /// let mut sink = Terminator::<_, 10>::new(logging.clone());
+    /// source.add_output().build::<Sig>().into_input(&mut sink)?;
+    ///
+    /// model!(source, sink).sampling_frequency(1e3).check()?.run().await?;
+    /// let logging = logging.lock().await;
+    /// let stamp = logging.stamps().last().unwrap();
+    /// assert_eq!((stamp.step, stamp.time), (90, Some(0.09)));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    pub fn sampling_frequency(mut self, sampling_frequency: f64) -> Self {
+        self.actors
+            .iter_mut()
+            .flatten()
+            .for_each(|actor| actor.set_sampling_frequency(sampling_frequency));
+        self
+    }
     /// Validates actors inputs and outputs
+    ///
+    /// Returns an error naming the actors and the inputs or outputs that are not properly connected
     pub fn check(self) -> Result<Model<Ready>> {
-        let (n_inputs, n_outputs) = self.n_io();
-        assert_eq!(
-            n_inputs, n_outputs,
-            "I/O #({},{}) don't match, did you forget to add some actors to the model?",
-            n_inputs, n_outputs
-        );
         match self.actors {
             Some(ref actors) => {
-                let mut inputs_hashes = vec![];
-                let mut outputs_hashes = vec![];
                 for actor in actors {
                     actor.check_inputs()?;
                     actor.check_outputs()?;
-                    inputs_hashes.append(&mut actor.inputs_hashes());
-                    outputs_hashes.append(&mut actor.outputs_hashes());
                 }
-                let hashes_diff = outputs_hashes
-                    .into_iter()
-                    .zip(inputs_hashes.into_iter())
-                    .map(|(o, i)| o as i128 - i as i128)
-                    .sum::<i128>();
-                assert_eq!(hashes_diff,0i128,
-                "I/O hashes difference: expected 0, found {}, did you forget to add some actors to the model?",
-                hashes_diff);
+                validation::validate(actors)?;
                 Ok(Model::<Ready> {
                     name: self.name,
                     actors: self.actors,
                     task_handles: None,
                     state: PhantomData,
                     start: Instant::now(),
+                    trace: None,
+                    handle: None,
+                    report: None,
+                    watchdog: None,
                 })
             }
             None => Err(ModelError::NoActors),
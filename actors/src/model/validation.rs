@@ -0,0 +1,309 @@
+//! Validation of the actors network
+//!
+//! Each pair of output/input shares a unique hash derived from the output actor and
+//! from the output data identifier.
+//! The hashes of the inputs are matched against the hashes of the outputs to find
+//! the connections that are missing one of their end.
+//!
+//! The feedback loops of the network are then checked for liveness:
+//! each loop must have at least one bootstrapped output and the rates of the actors
+//! around a loop must balance out.
+
+use std::collections::HashMap;
+
+use super::{Actors, ModelError, Result};
+use crate::actor::{gcd, plain::IO, PlainActor};
+
+fn short(name: &str) -> &str {
+    name.split('<').next().unwrap_or(name).split("::").last().unwrap()
+}
+
+fn io_name(io: &IO) -> &str {
+    match io {
+        IO::Bootstrap(data) | IO::Regular(data) | IO::Unbounded(data) => &data.name,
+    }
+}
+
+/// Checks that every output is connected to an input of an actor of the model and vice-versa
+///
+/// The errors are returned in the following order:
+///  1. [ModelError::DuplicatedConnections]: the same output is connected more than once to the same actor
+///     or several actors with the same name have the same output,
+///  2. [ModelError::MissingSenders]: the actor that owns the output an input is connected to is not in the model,
+///  3. [ModelError::DanglingOutputs]: some outputs have less inputs than senders,
+///  4. [ModelError::RateMismatch]: the rates at both ends of a connection differ (but for a [Reactor](crate::Reactor) or an input that does not wait for its data),
+///  5. the errors of the feedback loops checks.
+pub(crate) fn validate(actors: &Actors) -> Result<()> {
+    // output hash -> (actor, output, # of senders)
+    let mut producers: HashMap<u64, (String, String, usize)> = HashMap::new();
+    let mut duplicates = vec![];
+    let plains: Vec<_> = actors.iter().map(|actor| actor.as_plain()).collect();
+    for (actor, plain) in actors.iter().zip(&plains) {
+        let outputs_hashes = actor.outputs_hashes();
+        for output in plain.outputs.iter().flatten() {
+            let hash = output.get_hash();
+            let n = outputs_hashes.iter().filter(|&&h| h == hash).count();
+            let name = short(io_name(output)).to_string();
+            if producers
+                .insert(hash, (plain.client.clone(), name.clone(), n))
+                .is_some()
+            {
+                duplicates.push(format!("{}: {} (output)", plain.client, name));
+            }
+        }
+    }
+
+    // output hash -> # of inputs
+    let mut consumers: HashMap<u64, usize> = HashMap::new();
+    let mut missing = vec![];
+    for plain in &plains {
+        let mut hashes = vec![];
+        for input in plain.inputs.iter().flatten() {
+            let hash = input.get_hash();
+            let name = short(io_name(input));
+            if hashes.contains(&hash) {
+                duplicates.push(format!("{}: {} (input)", plain.client, name));
+            }
+            hashes.push(hash);
+            if producers.contains_key(&hash) {
+                *consumers.entry(hash).or_default() += 1;
+            } else {
+                missing.push(format!("{}: {}", plain.client, name));
+            }
+        }
+    }
+    if !duplicates.is_empty() {
+        return Err(ModelError::DuplicatedConnections(duplicates));
+    }
+    if !missing.is_empty() {
+        return Err(ModelError::MissingSenders(missing));
+    }
+
+    let mut dangling: Vec<_> = producers
+        .iter()
+        .filter_map(|(hash, (actor, output, n))| {
+            let m = consumers.get(hash).copied().unwrap_or_default();
+            (m < *n).then(|| format!("{}: {} ({}/{} inputs)", actor, output, m, n))
+        })
+        .collect();
+    if !dangling.is_empty() {
+        dangling.sort();
+        return Err(ModelError::DanglingOutputs(dangling));
+    }
+    let edges = edges(&plains);
+    check_rates(&plains, &edges)?;
+    check_loops(&plains, &edges)
+}
+
+/// Connection between the output of an actor and the input of another one
+struct Edge {
+    from: usize,
+    to: usize,
+    output: String,
+    bootstrap: bool,
+    /// the input does not wait for the data of the output
+    held: bool,
+}
+
+fn edges(actors: &[PlainActor]) -> Vec<Edge> {
+    let mut producers: HashMap<u64, (usize, &IO)> = HashMap::new();
+    for (i, actor) in actors.iter().enumerate() {
+        for output in actor.outputs.iter().flatten() {
+            producers.insert(output.get_hash(), (i, output));
+        }
+    }
+    let mut edges = vec![];
+    for (to, actor) in actors.iter().enumerate() {
+        for input in actor.inputs.iter().flatten() {
+            if let Some(&(from, output)) = producers.get(&input.get_hash()) {
+                edges.push(Edge {
+                    from,
+                    to,
+                    output: short(io_name(output)).to_string(),
+                    bootstrap: matches!(output, IO::Bootstrap(_)),
+                    held: !input.waits(),
+                });
+            }
+        }
+    }
+    edges
+}
+
+/// Checks that the outputs rate of each actor matches the inputs rate of the actors it is connected to
+///
+/// The inputs of event-driven actors, with a zero inputs rate, and the inputs that do not wait
+/// for their data accept the data at any rate
+fn check_rates(actors: &[PlainActor], edges: &[Edge]) -> Result<()> {
+    let mismatches: Vec<_> = edges
+        .iter()
+        .filter(|e| actors[e.to].inputs_rate > 0 && !e.held)
+        .filter(|e| actors[e.from].outputs_rate != actors[e.to].inputs_rate)
+        .map(|e| {
+            format!(
+                "{} of {} (rate {}) into {} (rate {})",
+                e.output,
+                actors[e.from].client,
+                actors[e.from].outputs_rate,
+                actors[e.to].client,
+                actors[e.to].inputs_rate
+            )
+        })
+        .collect();
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(ModelError::RateMismatch(mismatches))
+    }
+}
+
+/// Number of output samples per input sample as the fraction `(numerator, denominator)`
+fn gain(actor: &PlainActor) -> (usize, usize) {
+    (actor.inputs_rate, actor.outputs_rate)
+}
+
+/// Checks the feedback loops of the actors network
+///
+/// Returns [ModelError::UnbootstrappedLoops] if some loops have no bootstrapped output, each loop is
+/// reported with the output that must be bootstrapped to break it, and
+/// [ModelError::InconsistentLoopRates] if the samples of a loop do not come back at the rate
+/// they left.
+fn check_loops(actors: &[PlainActor], edges: &[Edge]) -> Result<()> {
+    let name = |i: usize| actors[i].client.as_str();
+
+    // depth-first search of the graph without the bootstrapped edges and the edges into
+    // the inputs that do not wait for their data: each back edge closes a loop that never starts
+    #[derive(Clone, Copy, PartialEq)]
+    enum Mark {
+        New,
+        Active,
+        Done,
+    }
+    let mut marks = vec![Mark::New; actors.len()];
+    let mut unbootstrapped = vec![];
+    for root in 0..actors.len() {
+        if marks[root] != Mark::New {
+            continue;
+        }
+        let mut path = vec![root];
+        let mut next_edge = vec![0usize];
+        marks[root] = Mark::Active;
+        while let Some(&node) = path.last() {
+            let k = next_edge.last_mut().unwrap();
+            match edges
+                .iter()
+                .filter(|e| e.from == node && !e.bootstrap && !e.held)
+                .nth(*k)
+            {
+                Some(edge) => {
+                    *k += 1;
+                    match marks[edge.to] {
+                        Mark::New => {
+                            marks[edge.to] = Mark::Active;
+                            path.push(edge.to);
+                            next_edge.push(0);
+                        }
+                        Mark::Active => {
+                            let start = path.iter().position(|&i| i == edge.to).unwrap();
+                            let cycle: Vec<_> = path[start..]
+                                .iter()
+                                .chain(Some(&edge.to))
+                                .map(|&i| name(i))
+                                .collect();
+                            unbootstrapped.push(format!(
+                                "{}: bootstrap {} of {}",
+                                cycle.join(" -> "),
+                                edge.output,
+                                name(edge.from)
+                            ));
+                        }
+                        Mark::Done => (),
+                    }
+                }
+                None => {
+                    marks[node] = Mark::Done;
+                    path.pop();
+                    next_edge.pop();
+                }
+            }
+        }
+    }
+    if !unbootstrapped.is_empty() {
+        return Err(ModelError::UnbootstrappedLoops(unbootstrapped));
+    }
+
+    // propagation of the inputs rate of each actor, relative to the first actor of a loop,
+    // along the edges of the loops (the held edges do not close any loop)
+    let reachable = |from: usize| {
+        let mut seen = vec![false; actors.len()];
+        let mut stack = vec![from];
+        while let Some(i) = stack.pop() {
+            for e in edges.iter().filter(|e| e.from == i && !e.held) {
+                if !seen[e.to] {
+                    seen[e.to] = true;
+                    stack.push(e.to);
+                }
+            }
+        }
+        seen
+    };
+    let reach: Vec<_> = (0..actors.len()).map(reachable).collect();
+    let mut rates: Vec<Option<(usize, usize)>> = vec![None; actors.len()];
+    let mut inconsistent = vec![];
+    // the rates are not propagated through the event-driven actors
+    let reactor = |i: usize| actors[i].inputs.is_some() && actors[i].inputs_rate == 0;
+    for root in 0..actors.len() {
+        if rates[root].is_some() || !reach[root][root] || reactor(root) {
+            continue;
+        }
+        let in_loop: Vec<_> = (0..actors.len())
+            .map(|i| reach[root][i] && reach[i][root])
+            .collect();
+        rates[root] = Some((1, 1));
+        let mut stack = vec![root];
+        'propagate: while let Some(i) = stack.pop() {
+            let (num, den) = rates[i].unwrap();
+            let (g_num, g_den) = gain(&actors[i]);
+            let (num, den) = (num * g_num, den * g_den);
+            let d = gcd(num, den);
+            let rate = (num / d, den / d);
+            for e in edges
+                .iter()
+                .filter(|e| e.from == i && in_loop[e.to] && !reactor(e.to))
+            {
+                match rates[e.to] {
+                    None => {
+                        rates[e.to] = Some(rate);
+                        stack.push(e.to);
+                    }
+                    Some(other) if other != rate => {
+                        let members: Vec<_> = (0..actors.len())
+                            .filter(|&i| in_loop[i])
+                            .map(name)
+                            .collect();
+                        inconsistent.push(format!(
+                            "{}: {} of {} into {} at {}/{} instead of {}/{}",
+                            members.join(", "),
+                            e.output,
+                            name(e.from),
+                            name(e.to),
+                            rate.0,
+                            rate.1,
+                            other.0,
+                            other.1
+                        ));
+                        break 'propagate;
+                    }
+                    _ => (),
+                }
+            }
+        }
+        // the other actors of the loop are not checked again
+        for i in (0..actors.len()).filter(|&i| in_loop[i]) {
+            rates[i].get_or_insert((1, 1));
+        }
+    }
+    if !inconsistent.is_empty() {
+        return Err(ModelError::InconsistentLoopRates(inconsistent));
+    }
+    Ok(())
+}
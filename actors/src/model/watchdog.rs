@@ -0,0 +1,91 @@
+//! Watchdog of a running model
+//!
+//! The watchdog periodically checks that the actors are still updating their clients.
+//! If none of them did within the timeout, the channels each actor is waiting on
+//! are written to the log and the model is aborted.
+
+use std::{
+    fmt::Write,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use super::ModelHandle;
+use crate::actor::{Monitor, Wait};
+use tokio::task::JoinHandle;
+
+/// Returns the channels each actor is waiting on
+fn dump(monitors: &[(String, Arc<Monitor>)]) -> String {
+    let mut dump = String::new();
+    for (name, monitor) in monitors {
+        let _ = writeln!(dump, "{} (#{} steps):", name, monitor.n_step());
+        let waits = monitor.waits();
+        if waits.is_empty() {
+            let _ = writeln!(dump, " - not waiting");
+        }
+        for wait in waits {
+            let _ = writeln!(dump, " - {}", wait);
+        }
+    }
+    dump
+}
+
+/// Watchdog task of a running model
+pub(super) struct Watchdog {
+    task: JoinHandle<()>,
+    /// channels dump, set before the model is aborted
+    dump: Arc<Mutex<Option<String>>>,
+}
+impl Watchdog {
+    /// Spawns the watchdog of the actors with the given monitors
+    pub(super) fn spawn(
+        monitors: Vec<(String, Arc<Monitor>)>,
+        handle: ModelHandle,
+        timeout: Duration,
+    ) -> Self {
+        let dump = Arc::new(Mutex::new(None));
+        let task = tokio::spawn(watch(monitors, handle, timeout, Arc::clone(&dump)));
+        Self { task, dump }
+    }
+    /// Stops the watchdog
+    ///
+    /// Returns the channels dump if the watchdog has aborted the model
+    pub(super) fn stop(self) -> Option<String> {
+        self.task.abort();
+        self.dump.lock().unwrap().take()
+    }
+}
+
+/// Watches the actors steps and aborts the model if they stalled for longer than `timeout`
+///
+/// The actors paused by the [ModelHandle] are not stalled and the watch ends once all the actors have exited.
+/// The channels dump is written into `slot` before the model is aborted.
+async fn watch(
+    monitors: Vec<(String, Arc<Monitor>)>,
+    handle: ModelHandle,
+    timeout: Duration,
+    slot: Arc<Mutex<Option<String>>>,
+) {
+    let n_step = || monitors.iter().map(|(_, m)| m.n_step()).sum::<usize>();
+    let paused = || {
+        monitors
+            .iter()
+            .any(|(_, m)| m.waits().contains(&Wait::Paused))
+    };
+    let mut last = n_step();
+    loop {
+        tokio::time::sleep(timeout).await;
+        if monitors.iter().all(|(_, m)| m.has_exited()) {
+            return;
+        }
+        let current = n_step();
+        if current == last && !paused() {
+            let dump = dump(&monitors);
+            log::error!("model stalled for {:?}, aborting:\n{}", timeout, dump);
+            *slot.lock().unwrap() = Some(dump);
+            handle.abort();
+            return;
+        }
+        last = current;
+    }
+}
@@ -38,6 +38,26 @@ where
     rxs: Vec<Rx<U>>,
     client: Arc<tokio::sync::Mutex<C>>,
 }
+impl<U, C, const NI: usize, const NO: usize> OutputRx<U, C, NI, NO>
+where
+    U: UniqueIdentifier + Send + Sync,
+    C: Update + io::Write<U>,
+{
+    /// Returns the output hash and the receivers
+    pub(crate) fn into_parts(self) -> (u64, Vec<Rx<U>>) {
+        (self.hash, self.rxs)
+    }
+    /// Returns the output hash and the last receiver
+    #[cfg(feature = "transport")]
+    pub(crate) fn pop(&mut self) -> Option<(u64, Rx<U>)> {
+        self.rxs.pop().map(|rx| (self.hash, rx))
+    }
+    /// Checks if all the receivers have been assigned
+    #[cfg(feature = "transport")]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.rxs.is_empty()
+    }
+}
 pub trait TryIntoInputs<U, CO, const NO: usize, const NI: usize>
 where
     Assoc<U>: Send + Sync,
@@ -45,12 +65,45 @@ where
     CO: 'static + Update + Send + io::Write<U>,
 {
     /// Try to create a new input for 'actor' from the last 'Receiver'
-    fn into_input<CI, const N: usize>(self, actor: &mut Actor<CI, NO, N>) -> Self
+    ///
+    /// The input is added either to an [Actor] or to a [SubSystem](crate::subsystem::SubSystem)
+    fn into_input<A>(self, actor: &mut A) -> Self
     where
-        CI: 'static + Update + Send + io::Read<U>,
+        A: InputPort<U> + InputsRate<NO>,
+        Self: Sized;
+    /// Try to create a new input for 'actor' from the last 'Receiver' with the given [InputMode]
+    ///
+    /// The rates of the output and of the input are checked when the [Model](crate::model::Model) is checked,
+    /// and only if the input waits for its data
+    fn into_input_with_mode<C, const N: usize, const M: usize>(
+        self,
+        actor: &mut Actor<C, N, M>,
+        mode: InputMode,
+    ) -> Self
+    where
+        C: 'static + Update + Send + io::Read<U>,
         Self: Sized;
 }
 
+/// Actors, or sub-systems, with an input for the data `U`
+pub trait InputPort<U: UniqueIdentifier> {
+    /// Adds an input for the receiver of an output
+    #[doc(hidden)]
+    fn add_port(&mut self, rx: Rx<U>, hash: u64);
+}
+
+/// Actors, or sub-systems, with an output for the data `U`
+pub trait OutputPort<U: UniqueIdentifier> {
+    /// Adds an output and returns its hash and its receivers
+    #[doc(hidden)]
+    fn build_port(&mut self, builder: ActorOutputBuilder) -> (u64, Vec<Rx<U>>);
+}
+
+/// Inputs rate of actors
+///
+/// The inputs rate of sub-systems is checked when the [Model](crate::model::Model) is checked
+pub trait InputsRate<const NI: usize> {}
+
 /// Assign a new entry to a logging actor
 #[async_trait]
 pub trait IntoLogsN<CI, const N: usize, const NO: usize>
@@ -73,10 +126,128 @@ where
         Self: Sized;
 }
 
+/// Policy of an output when the bounded channel to an input is full
+///
+/// With any other policy than [Overflow::Block], the output never waits for the receiving actor:
+/// the data is either dropped or it replaces the oldest data in the channel.
+/// The number of dropped data is given by the [ActorReport](crate::model::ActorReport) of the actor.
+/// The output keeps a receiving end of the channel with [Overflow::DropOldest] and [Overflow::Latest]
+/// and it ends, as with the other policies, once that receiving end is the only one left.
+/// ```
+/// # tokio_test::block_on(async {
+/// use gmt_dos_actors::prelude::*;
+/// use gmt_dos_clients::{interface::UID, Logging, Signals};
+/// #[derive(UID)]
+/// enum Source {};
+/// let mut source: Initiator<_> = Signals::new(1, 100).into();
+/// let logging = Logging::<f64>::default().into_arcx();
+/// let mut sink = Terminator::<_>::new(logging.clone());
+/// let monitoring = Logging::<f64>::default().into_arcx();
+/// let mut monitor = Terminator::<_>::new(monitoring.clone());
+/// source.add_output().build::<Source>().into_input(&mut sink)?;
+/// source
+///     .add_output()
+///     .capacity(10)
+///     .overflow(Overflow::DropOldest)
+///     .build::<Source>()
+///     .into_input(&mut monitor)?;
+/// model!(source, sink, monitor).check()?.run().await?;
+/// assert_eq!(logging.lock().await.len(), 100);
+/// assert!(monitoring.lock().await.len() <= 100);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "loader", derive(serde::Deserialize), serde(rename_all = "kebab-case"))]
+pub enum Overflow {
+    /// Waits for the receiving actor to make room in the channel
+    #[default]
+    Block,
+    /// Drops the oldest data in the channel
+    DropOldest,
+    /// Drops the new data
+    DropNewest,
+    /// Keeps only the latest data, the capacity of the channel is set to 1
+    Latest,
+}
+
+/// Mode of an actor input
+///
+/// With any other mode than [InputMode::Wait], the input never waits for the sending actor:
+/// if no data has arrived, the client keeps the last value it has read.
+/// Such an input can be connected to an output with a different rate and, as it does not close
+/// a feedback loop, the output does not need to be bootstrapped.
+/// An actor must have at least one input in the [InputMode::Wait] mode.
+///
+/// The mode is given when the input is created with [TryIntoInputs::into_input_with_mode]
+/// or set afterwards with [Actor::input_mode].
+/// ```
+/// # tokio_test::block_on(async {
+/// use gmt_dos_actors::prelude::*;
+/// use gmt_dos_clients::{
+///     interface::{Data, Read, Update, UID},
+///     Sampler, Signals,
+/// };
+/// #[derive(UID)]
+/// enum Fast {}
+/// #[derive(UID)]
+/// enum Slow {}
+/// #[derive(Default)]
+/// struct Fusion {
+///     n_fast: usize,
+///     n_slow: usize,
+/// }
+/// impl Update for Fusion {}
+/// impl Read<Fast> for Fusion {
+///     fn read(&mut self, _: Data<Fast>) {
+///         self.n_fast += 1;
+///     }
+/// }
+/// impl Read<Slow> for Fusion {
+///     fn read(&mut self, _: Data<Slow>) {
+///         self.n_slow += 1;
+///     }
+/// }
+///
+/// let mut source: Initiator<_> = Signals::new(1, 100).into();
+/// let mut decimator: Actor<_, 1, 10> = Sampler::<Vec<f64>, Fast, Slow>::default().into();
+/// let fusion = Fusion::default().into_arcx();
+/// let mut sink = Terminator::<_>::new(fusion.clone());
+/// source
+///     .add_output()
+///     .multiplex(2)
+///     .build::<Fast>()
+///     .into_input(&mut decimator)
+///     .into_input(&mut sink)?;
+/// decimator
+///     .add_output()
+///     .build::<Slow>()
+///     .into_input_with_mode(&mut sink, InputMode::Optional)?;
+///
+/// model!(source, decimator, sink).check()?.run().await?;
+/// let fusion = fusion.lock().await;
+/// assert_eq!(fusion.n_fast, 100);
+/// assert!(fusion.n_slow <= 10);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "loader", derive(serde::Deserialize), serde(rename_all = "kebab-case"))]
+pub enum InputMode {
+    /// Waits for the data of every cycle of the actor
+    #[default]
+    Wait,
+    /// Reads the next data in the channel, if any
+    Optional,
+    /// Reads only the newest data in the channel, if any, the older data being discarded
+    Latest,
+}
+
 /// Actor outputs builder
 pub struct ActorOutputBuilder {
     capacity: Vec<usize>,
     bootstrap: bool,
+    overflow: Overflow,
 }
 
 type Rx<U> = flume::Receiver<io::Data<U>>;
@@ -88,6 +259,10 @@ where
 {
     /// Sets the channel to unbounded
     fn unbounded(self) -> Self;
+    /// Sets the capacity of the bounded channel (default: 1)
+    fn capacity(self, capacity: usize) -> Self;
+    /// Sets the policy of the output when the channel is full (default: [Overflow::Block])
+    fn overflow(self, overflow: Overflow) -> Self;
     /// Flags the output to be bootstrapped
     fn bootstrap(self) -> Self;
     /// Multiplexes the output `n` times
@@ -9,7 +9,10 @@ use std::{
     hash::{Hash, Hasher},
 };
 
-use super::{ IntoInputs, IntoLogs, IntoLogsN, OutputRx, TryIntoInputs};
+use super::{
+    ActorOutputBuilder, AddOuput, InputMode, InputPort, InputsRate, IntoInputs, IntoLogs,
+    IntoLogsN, OutputPort, OutputRx, Rx, TryIntoInputs,
+};
 
 // Unique hash for a pair of input/output
 fn hashio<CO, const NO: usize, const NI: usize>(output_actor: &mut Actor<CO, NI, NO>) -> u64
@@ -72,16 +75,36 @@ where
     U: 'static + Send + Sync + UniqueIdentifier,
     CO: 'static + Update + Send + io::Write<U>,
 {
-    fn into_input<CI, const N: usize>(mut self, actor: &mut Actor<CI, NO, N>) -> Self
+    fn into_input<A>(mut self, actor: &mut A) -> Self
     where
-        CI: 'static + Update + Send + io::Read<U>,
+        A: InputPort<U> + InputsRate<NO>,
         Self: Sized,
     {
         let Err(OutputRx{ hash, ref mut rxs,.. }) = self else { 
             panic!(r#"Input receivers have been exhausted"#) 
         };
         let Some(recv) = rxs.pop() else { panic!(r#"Input receivers is empty"#) };
-        actor.add_input(recv, hash);
+        actor.add_port(recv, hash);
+        if rxs.is_empty() {
+            Ok(())
+        } else {
+            self
+        }
+    }
+    fn into_input_with_mode<C, const N: usize, const M: usize>(
+        mut self,
+        actor: &mut Actor<C, N, M>,
+        mode: InputMode,
+    ) -> Self
+    where
+        C: 'static + Update + Send + io::Read<U>,
+        Self: Sized,
+    {
+        let Err(OutputRx { hash, ref mut rxs, .. }) = self else {
+            panic!(r#"Input receivers have been exhausted"#)
+        };
+        let Some(recv) = rxs.pop() else { panic!(r#"Input receivers is empty"#) };
+        actor.add_input_with_mode(recv, hash, mode);
         if rxs.is_empty() {
             Ok(())
         } else {
@@ -90,6 +113,30 @@ where
     }
 }
 
+impl<U, C, const NI: usize, const N: usize> InputPort<U> for Actor<C, NI, N>
+where
+    Assoc<U>: Send + Sync,
+    U: 'static + Send + Sync + UniqueIdentifier,
+    C: 'static + Update + Send + io::Read<U>,
+{
+    fn add_port(&mut self, rx: Rx<U>, hash: u64) {
+        self.add_input(rx, hash)
+    }
+}
+
+impl<U, C, const NI: usize, const NO: usize> OutputPort<U> for Actor<C, NI, NO>
+where
+    Assoc<U>: Send + Sync,
+    U: 'static + Send + Sync + UniqueIdentifier,
+    C: 'static + Update + Send + io::Write<U>,
+{
+    fn build_port(&mut self, builder: ActorOutputBuilder) -> (u64, Vec<Rx<U>>) {
+        (self, builder).build::<U>().unwrap_err().into_parts()
+    }
+}
+
+impl<C, const NI: usize, const N: usize> InputsRate<NI> for Actor<C, NI, N> where C: Update + Send {}
+
 impl<U, CO, const NO: usize, const NI: usize> std::error::Error for OutputRx<U, CO, NI, NO>
 where
     U: 'static + UniqueIdentifier + Send + Sync,
@@ -1,8 +1,9 @@
 use crate::interface as io;
 use crate::{
-    io::{Output, OutputObject, S},
+    io::{Output, OutputObject, Policy, S},
     Actor, Who,
 };
+use flume::Sender;
 use std::{
     collections::hash_map::DefaultHasher,
     hash::{Hash, Hasher},
@@ -10,13 +11,17 @@ use std::{
 
 use crate::{Assoc, UniqueIdentifier, Update};
 
-use super::{ActorOutputBuilder, AddOuput, OutputRx, Rx};
+use super::{ActorOutputBuilder, AddOuput, OutputRx, Overflow, Rx};
+
+// Senders, overflow policies and receivers of the channels of an output
+type Channels<U> = (Vec<Sender<S<U>>>, Vec<Policy<U>>, Vec<Rx<U>>);
 
 impl Default for ActorOutputBuilder {
     fn default() -> Self {
         Self {
             capacity: Vec::new(),
             bootstrap: false,
+            overflow: Overflow::default(),
         }
     }
 }
@@ -28,6 +33,64 @@ impl ActorOutputBuilder {
             ..Default::default()
         }
     }
+    /// Sets the channels to unbounded
+    pub(crate) fn unbounded(self) -> Self {
+        let n = self.capacity.len();
+        Self {
+            capacity: vec![usize::MAX; n],
+            ..self
+        }
+    }
+    /// Sets the capacity of the bounded channels
+    pub(crate) fn capacity(self, capacity: usize) -> Self {
+        assert!(capacity > 0, "the channel capacity must be greater than 0");
+        let n = self.capacity.len();
+        Self {
+            capacity: vec![capacity; n],
+            ..self
+        }
+    }
+    /// Sets the policy of the output when a channel is full
+    pub(crate) fn overflow(self, overflow: Overflow) -> Self {
+        Self { overflow, ..self }
+    }
+    /// Flags the output to be bootstrapped
+    pub(crate) fn bootstrap(self) -> Self {
+        Self {
+            bootstrap: true,
+            ..self
+        }
+    }
+    /// Multiplexes the output `n` times
+    pub(crate) fn multiplex(self, n: usize) -> Self {
+        Self {
+            capacity: vec![self.capacity[0]; n],
+            ..self
+        }
+    }
+    /// Creates the channels of the output
+    ///
+    /// Returns the senders, their overflow policies and the receivers
+    fn channels<U: UniqueIdentifier>(&self) -> Channels<U> {
+        let mut txs = vec![];
+        let mut policies = vec![];
+        let mut rxs = vec![];
+        for &cap in &self.capacity {
+            let (tx, rx) = match (cap, self.overflow) {
+                (usize::MAX, _) => flume::unbounded::<S<U>>(),
+                (_, Overflow::Latest) => flume::bounded::<S<U>>(1),
+                _ => flume::bounded::<S<U>>(cap),
+            };
+            policies.push(match self.overflow {
+                Overflow::Block => Policy::Block,
+                Overflow::DropNewest => Policy::DropNewest,
+                Overflow::DropOldest | Overflow::Latest => Policy::DropOldest(rx.clone()),
+            });
+            txs.push(tx);
+            rxs.push(rx);
+        }
+        (txs, policies, rxs)
+    }
 }
 
 impl<'a, C, const NI: usize, const NO: usize> AddOuput<'a, C, NI, NO>
@@ -36,32 +99,19 @@ where
     C: 'static + Update + Send,
 {
     fn unbounded(self) -> Self {
-        let n = self.1.capacity.len();
-        (
-            self.0,
-            ActorOutputBuilder {
-                capacity: vec![usize::MAX; n],
-                ..self.1
-            },
-        )
+        (self.0, self.1.unbounded())
+    }
+    fn capacity(self, capacity: usize) -> Self {
+        (self.0, self.1.capacity(capacity))
+    }
+    fn overflow(self, overflow: Overflow) -> Self {
+        (self.0, self.1.overflow(overflow))
     }
     fn bootstrap(self) -> Self {
-        (
-            self.0,
-            ActorOutputBuilder {
-                bootstrap: true,
-                ..self.1
-            },
-        )
+        (self.0, self.1.bootstrap())
     }
     fn multiplex(self, n: usize) -> Self {
-        (
-            self.0,
-            ActorOutputBuilder {
-                capacity: vec![self.1.capacity[0]; n],
-                ..self.1
-            },
-        )
+        (self.0, self.1.multiplex(n))
     }
     fn legacy_build<U>(self) -> (&'a mut Actor<C, NI, NO>, Vec<Rx<U>>)
     where
@@ -70,21 +120,11 @@ where
         Assoc<U>: Send + Sync,
     {
         let (actor, builder) = self;
-        let mut txs = vec![];
-        let mut rxs = vec![];
-        for &cap in &builder.capacity {
-            let (tx, rx) = if cap == usize::MAX {
-                flume::unbounded::<S<U>>()
-            } else {
-                flume::bounded::<S<U>>(cap)
-            };
-            txs.push(tx);
-            rxs.push(rx);
-        }
+        let (txs, policies, rxs) = builder.channels::<U>();
 
         let output: Output<C, Assoc<U>, U, NO> = Output::builder(actor.client.clone())
             .bootstrap(builder.bootstrap)
-            .senders(txs)
+            .senders(txs, policies)
             .build();
 
         if let Some(ref mut outputs) = actor.outputs {
@@ -102,17 +142,7 @@ where
         Assoc<U>: Send + Sync,
     {
         let (actor, builder) = self;
-        let mut txs = vec![];
-        let mut rxs = vec![];
-        for &cap in &builder.capacity {
-            let (tx, rx) = if cap == usize::MAX {
-                flume::unbounded::<S<U>>()
-            } else {
-                flume::bounded::<S<U>>(cap)
-            };
-            txs.push(tx);
-            rxs.push(rx);
-        }
+        let (txs, policies, rxs) = builder.channels::<U>();
 
         // Check if this output already exists
         if let Some(outputs) = &mut actor.outputs {
@@ -121,7 +151,7 @@ where
                     .as_mut_any()
                     .downcast_mut::<Output<C, Assoc<U>, U, NO>>()
             }) {
-                output.tx_push(txs);
+                output.tx_push(txs, policies);
                 let output_name = Who::who(output);
                 return Err(OutputRx {
                     hash: output.get_hash(),
@@ -135,7 +165,7 @@ where
 
         let mut output: Output<C, Assoc<U>, U, NO> = Output::builder(actor.client.clone())
             .bootstrap(builder.bootstrap)
-            .senders(txs)
+            .senders(txs, policies)
             .build();
 
         let mut hasher = DefaultHasher::new();
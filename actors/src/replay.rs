@@ -0,0 +1,561 @@
+/*!
+# Record and replay
+
+The messages sent on selected channels of a [model](crate::model) are recorded into a file by a [Recorder].
+The [Recorder] is attached to the outputs of the actors with [Actor::record](crate::Actor::record),
+each message being written with the full name of the UID, the output hash and the index of the message on the channel, the step.
+The outputs of the same UID are recorded separately, but the data of a UID must come from a single output
+to be fed to or to be compared with a client.
+
+A [Replay] is a standalone harness for a single client:
+the client reads the recorded inputs and the data written to its outputs is compared
+to the recorded outputs within a given tolerance, following the inputs and outputs rates of the actor
+the client has been recorded with.
+A client can then be regression-tested against data captured in a full model,
+without any of the other clients of the model.
+
+The data is serialized with [bincode](https://docs.rs/bincode), so the data type of the UIDs must implement
+[serde](https://docs.rs/serde)'s `Serialize` and `Deserialize` traits.
+
+```
+# tokio_test::block_on(async {
+use gmt_dos_actors::{
+    prelude::*,
+    replay::{Recorder, Recording, Replay},
+};
+use gmt_dos_clients::{interface::UID, Logging, Sampler, Signals};
+#[derive(UID)]
+enum In {};
+#[derive(UID)]
+enum Out {};
+# let path = std::env::temp_dir().join("recording.bin");
+
+let mut source: Initiator<_> = Signals::new(1, 100).into();
+let mut sampler: Actor<_> = Sampler::<Vec<f64>, In, Out>::default().into();
+let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+source.add_output().build::<In>().into_input(&mut sampler)?;
+sampler.add_output().build::<Out>().into_input(&mut sink)?;
+
+let recorder = Recorder::new(&path)?.into_arcx();
+source.record::<In>(&recorder)?;
+sampler.record::<Out>(&recorder)?;
+model!(source, sampler, sink).check()?.run().await?;
+recorder.lock().await.flush()?;
+
+let report = Replay::new(Sampler::<Vec<f64>, In, Out>::default())
+    .input::<In>()
+    .output::<Out>(1e-12)
+    .run(&Recording::from_path(&path)?)?;
+println!("{report}");
+assert_eq!(report.n_step, 100);
+# Ok::<(), Box<dyn std::error::Error>>(())
+# });
+```
+*/
+
+use crate::{
+    actor::ProbeObject,
+    interface::{Assoc, Data, Read, UniqueIdentifier, Update, Write},
+    io::{OutputObject, S},
+    uid::uid_name,
+    Actor,
+};
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    any::type_name,
+    collections::HashMap,
+    fmt::Display,
+    fs::File,
+    io::{self, BufReader, BufWriter, Write as _},
+    path::Path,
+    sync::Arc,
+};
+use tokio::sync::Mutex;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayError {
+    #[error("failed to read or to write the recording")]
+    Io(#[from] io::Error),
+    #[error("failed to serialize or to deserialize the recording")]
+    Bincode(#[from] bincode::Error),
+    #[error(r#"no "{0}" data in the recording"#)]
+    Missing(String),
+    #[error(r#""{0}" data is recorded from {1} outputs"#)]
+    Ambiguous(String, usize),
+    #[error(r#""{uid}" data #{step} is missing from the recording"#)]
+    Gap { uid: String, step: usize },
+    #[error(r#"the client did not write "{uid}" data #{step}"#)]
+    NoData { uid: String, step: usize },
+    #[error(r#""{uid}" data #{step} has {found} values instead of {expected}"#)]
+    Size {
+        uid: String,
+        step: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error(r#""{uid}" data #{step} differs from the recording by {error:e} (tolerance: {tolerance:e})"#)]
+    Mismatch {
+        uid: String,
+        step: usize,
+        error: f64,
+        tolerance: f64,
+    },
+    #[error("the replay has neither inputs nor outputs")]
+    Empty,
+}
+type Result<T> = std::result::Result<T, ReplayError>;
+
+/// Recorded message
+#[derive(Debug, Serialize, Deserialize)]
+struct Message {
+    uid: String,
+    hash: u64,
+    step: usize,
+    data: Vec<u8>,
+}
+
+/// Recorder of the messages of a model
+///
+/// The recorder records the data of any output which data type is serializable.
+/// The messages are written to the file as they are received, the file must be flushed
+/// with [Recorder::flush] once the model has completed.
+pub struct Recorder {
+    writer: BufWriter<File>,
+    steps: HashMap<(String, u64), usize>,
+    error: Option<ReplayError>,
+}
+impl Recorder {
+    /// Creates a new recorder writing to the file at `path`
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(Self {
+            writer: BufWriter::new(File::create(path)?),
+            steps: HashMap::new(),
+            error: None,
+        })
+    }
+    /// Flushes the recording to the file
+    ///
+    /// Returns the first error that occurred while recording, if any
+    pub fn flush(&mut self) -> Result<()> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        Ok(self.writer.flush()?)
+    }
+}
+impl Update for Recorder {}
+impl Recorder {
+    /// Writes the `U` data of the output with the given hash
+    fn record<U>(&mut self, hash: u64, data: &Data<U>)
+    where
+        U: UniqueIdentifier,
+        Assoc<U>: Serialize,
+    {
+        if self.error.is_some() {
+            return;
+        }
+        let uid = uid_name::<U>();
+        let step = self.steps.entry((uid.clone(), hash)).or_default();
+        let message = bincode::serialize(data).and_then(|data| {
+            bincode::serialize_into(
+                &mut self.writer,
+                &Message {
+                    uid,
+                    hash,
+                    step: *step,
+                    data,
+                },
+            )
+        });
+        *step += 1;
+        if let Err(e) = message {
+            log::warn!("recording {} data failed: {e}", type_name::<U>());
+            self.error = Some(e.into());
+        }
+    }
+}
+
+/// Probe of an output recording the output data with a [Recorder]
+struct RecorderProbe {
+    recorder: Arc<Mutex<Recorder>>,
+    hash: u64,
+}
+#[async_trait]
+impl<U> ProbeObject<U> for RecorderProbe
+where
+    U: 'static + UniqueIdentifier,
+    Assoc<U>: Send + Sync + Serialize,
+{
+    async fn record(&mut self, data: &Data<U>) {
+        self.recorder.lock().await.record(self.hash, data);
+    }
+}
+
+impl<C, const NI: usize, const NO: usize> Actor<C, NI, NO>
+where
+    C: 'static + Update + Send,
+{
+    /// Records the data sent by the output `U` with the [Recorder]
+    ///
+    /// An error is returned if the actor has no output `U`
+    pub fn record<U>(&mut self, recorder: &Arc<Mutex<Recorder>>) -> crate::Result<&mut Self>
+    where
+        C: Write<U>,
+        U: 'static + Send + Sync + UniqueIdentifier,
+        Assoc<U>: Send + Sync + Serialize,
+    {
+        let output = self.probed_output::<U>()?;
+        let probe = RecorderProbe {
+            recorder: Arc::clone(recorder),
+            hash: output.get_hash(),
+        };
+        output.probe_push(Box::new(probe));
+        Ok(self)
+    }
+}
+
+/// Messages of a recording
+#[derive(Debug, Default)]
+pub struct Recording {
+    messages: HashMap<(String, u64), Vec<Vec<u8>>>,
+}
+impl Recording {
+    /// Reads a recording from a file
+    ///
+    /// An error is returned if a message is missing on any of the recorded channels
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut messages: HashMap<(String, u64), Vec<Vec<u8>>> = HashMap::new();
+        loop {
+            let message: Message = match bincode::deserialize_from(&mut reader) {
+                Ok(message) => message,
+                Err(e) => match *e {
+                    bincode::ErrorKind::Io(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                        break
+                    }
+                    _ => return Err(e.into()),
+                },
+            };
+            let data = messages
+                .entry((message.uid.clone(), message.hash))
+                .or_default();
+            if message.step != data.len() {
+                return Err(ReplayError::Gap {
+                    uid: message.uid,
+                    step: data.len(),
+                });
+            }
+            data.push(message.data);
+        }
+        Ok(Self { messages })
+    }
+    /// Returns the full names of the recorded UIDs and the hashes of their outputs
+    pub fn uids(&self) -> Vec<(&str, u64)> {
+        self.messages
+            .keys()
+            .map(|(uid, hash)| (uid.as_str(), *hash))
+            .collect()
+    }
+    /// Returns the recorded `U` data
+    pub fn data<U>(&self) -> Result<Vec<Data<U>>>
+    where
+        U: UniqueIdentifier,
+        Assoc<U>: DeserializeOwned,
+    {
+        self.messages(&uid_name::<U>())?
+            .iter()
+            .map(|bytes| Ok(bincode::deserialize(bytes)?))
+            .collect()
+    }
+    /// Returns the messages of the single output of `uid`
+    fn messages(&self, uid: &str) -> Result<&[Vec<u8>]> {
+        let mut outputs = self
+            .messages
+            .iter()
+            .filter(|((name, _), _)| name == uid)
+            .map(|(_, messages)| messages.as_slice());
+        match (outputs.next(), outputs.count()) {
+            (None, _) => Err(ReplayError::Missing(uid.to_string())),
+            (Some(messages), 0) => Ok(messages),
+            (Some(_), n) => Err(ReplayError::Ambiguous(uid.to_string(), n + 1)),
+        }
+    }
+}
+
+/// Data types that are compared to the recording
+pub trait Compare {
+    /// Returns the number of values
+    fn size(&self) -> usize;
+    /// Returns the largest absolute difference with `other` of the same size
+    ///
+    /// The difference is NaN if a value is NaN on one side only
+    fn max_error(&self, other: &Self) -> f64;
+}
+impl<T: Copy + Into<f64>> Compare for Vec<T> {
+    fn size(&self) -> usize {
+        self.len()
+    }
+    fn max_error(&self, other: &Self) -> f64 {
+        self.iter()
+            .zip(other)
+            .map(|(&x, &y)| {
+                let (x, y): (f64, f64) = (x.into(), y.into());
+                if x == y || (x.is_nan() && y.is_nan()) {
+                    0f64
+                } else {
+                    (x - y).abs()
+                }
+            })
+            // a NaN difference is kept
+            .fold(0f64, |max, error| {
+                if error.is_nan() || error > max {
+                    error
+                } else {
+                    max
+                }
+            })
+    }
+}
+
+/// Client input fed with recorded data
+struct Feed<C> {
+    uid: String,
+    read: fn(&mut C, &[u8]) -> bincode::Result<()>,
+}
+impl<C> Feed<C> {
+    fn new<U>() -> Self
+    where
+        C: Read<U>,
+        U: UniqueIdentifier,
+        Assoc<U>: DeserializeOwned,
+    {
+        Self {
+            uid: uid_name::<U>(),
+            read: |client, bytes| {
+                client.read(bincode::deserialize::<S<U>>(bytes)?);
+                Ok(())
+            },
+        }
+    }
+}
+
+/// Client output compared to recorded data
+struct Check<C> {
+    uid: String,
+    tolerance: f64,
+    bootstrap: bool,
+    // returns the largest absolute difference between the client and the recorded data
+    compare: fn(&mut C, &[u8], &str, usize) -> Result<f64>,
+}
+impl<C> Check<C> {
+    fn new<U>(tolerance: f64, bootstrap: bool) -> Self
+    where
+        C: Write<U>,
+        U: UniqueIdentifier,
+        Assoc<U>: Compare + DeserializeOwned,
+    {
+        Self {
+            uid: uid_name::<U>(),
+            tolerance,
+            bootstrap,
+            compare: |client, bytes, uid, step| {
+                let recorded: S<U> = bincode::deserialize(bytes)?;
+                let data = <C as Write<U>>::write(client).ok_or_else(|| ReplayError::NoData {
+                    uid: uid.to_string(),
+                    step,
+                })?;
+                if data.size() != recorded.size() {
+                    return Err(ReplayError::Size {
+                        uid: uid.to_string(),
+                        step,
+                        expected: recorded.size(),
+                        found: data.size(),
+                    });
+                }
+                Ok(data.max_error(&recorded))
+            },
+        }
+    }
+}
+
+/// Comparison of a client output to the recording
+#[derive(Debug, Clone)]
+pub struct OutputReport {
+    /// UID name
+    pub uid: String,
+    /// Number of data compared
+    pub n_sample: usize,
+    /// Largest absolute difference with the recorded data
+    pub max_error: f64,
+}
+
+/// Summary of a [Replay]
+#[derive(Debug, Clone)]
+pub struct ReplayReport {
+    /// Number of client updates
+    pub n_step: usize,
+    pub outputs: Vec<OutputReport>,
+}
+impl Display for ReplayReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Replay of {} steps:", self.n_step)?;
+        for output in &self.outputs {
+            writeln!(
+                f,
+                " - {}: {} samples, max. error: {:e}",
+                output.uid, output.n_sample, output.max_error
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Replay harness of a client
+///
+/// The harness mimics the loop of an actor with inputs rate `ni` and outputs rate `no`:
+/// the recorded inputs are read every `ni` steps, followed by an update of the client,
+/// and the outputs are written and compared to the recording every `no` steps,
+/// until the recorded inputs or outputs are exhausted.
+pub struct Replay<C> {
+    client: C,
+    ni: usize,
+    no: usize,
+    inputs: Vec<Feed<C>>,
+    outputs: Vec<Check<C>>,
+}
+impl<C: Update> Replay<C> {
+    /// Creates a new replay harness for the client, with unit inputs and outputs rates
+    pub fn new(client: C) -> Self {
+        Self {
+            client,
+            ni: 1,
+            no: 1,
+            inputs: vec![],
+            outputs: vec![],
+        }
+    }
+    /// Sets the inputs rate `ni` and the outputs rate `no`
+    pub fn rates(self, ni: usize, no: usize) -> Self {
+        assert!(ni > 0 && no > 0, "the replay rates must be greater than 0");
+        Self { ni, no, ..self }
+    }
+    /// Feeds the client with the recorded `U` data
+    pub fn input<U>(mut self) -> Self
+    where
+        C: Read<U>,
+        U: UniqueIdentifier,
+        Assoc<U>: DeserializeOwned,
+    {
+        self.inputs.push(Feed::new::<U>());
+        self
+    }
+    /// Compares the `U` data written by the client to the recorded data
+    ///
+    /// The data type of `U` must implement [Compare]: the comparison fails if the largest absolute difference
+    /// is greater than `tolerance` or if it is not finite
+    pub fn output<U>(mut self, tolerance: f64) -> Self
+    where
+        C: Write<U>,
+        U: UniqueIdentifier,
+        Assoc<U>: Compare + DeserializeOwned,
+    {
+        self.outputs.push(Check::new::<U>(tolerance, false));
+        self
+    }
+    /// Compares the `U` data written by the client to the recorded data of a bootstrapped output
+    ///
+    /// The first recorded data is compared to the data written by the client before its first update
+    pub fn bootstrap_output<U>(mut self, tolerance: f64) -> Self
+    where
+        C: Write<U>,
+        U: UniqueIdentifier,
+        Assoc<U>: Compare + DeserializeOwned,
+    {
+        self.outputs.push(Check::new::<U>(tolerance, true));
+        self
+    }
+    /// Replays the recording
+    ///
+    /// Returns an error at the first output that differs from the recording
+    pub fn run(mut self, recording: &Recording) -> Result<ReplayReport> {
+        if self.inputs.is_empty() && self.outputs.is_empty() {
+            return Err(ReplayError::Empty);
+        }
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|feed| recording.messages(&feed.uid))
+            .collect::<Result<Vec<_>>>()?;
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|check| recording.messages(&check.uid))
+            .collect::<Result<Vec<_>>>()?;
+        let mut reports: Vec<_> = self
+            .outputs
+            .iter()
+            .map(|check| OutputReport {
+                uid: check.uid.clone(),
+                n_sample: 0,
+                max_error: 0f64,
+            })
+            .collect();
+
+        // compares the outputs to the recording, returns false if the recording is exhausted
+        let mut distribute = |client: &mut C, bootstrap: bool| -> Result<bool> {
+            for ((check, messages), report) in self.outputs.iter().zip(&outputs).zip(&mut reports)
+            {
+                if bootstrap && !check.bootstrap {
+                    continue;
+                }
+                let step = report.n_sample;
+                let Some(bytes) = messages.get(step) else {
+                    return Ok(false);
+                };
+                let error = (check.compare)(client, bytes, &check.uid, step)?;
+                // a NaN error fails the comparison
+                #[allow(clippy::neg_cmp_op_on_partial_ord)]
+                if !(error <= check.tolerance) {
+                    return Err(ReplayError::Mismatch {
+                        uid: check.uid.clone(),
+                        step,
+                        error,
+                        tolerance: check.tolerance,
+                    });
+                }
+                report.max_error = report.max_error.max(error);
+                report.n_sample += 1;
+            }
+            Ok(true)
+        };
+
+        let mut n_step = 0;
+        if !distribute(&mut self.client, true)? {
+            return Ok(ReplayReport {
+                n_step,
+                outputs: reports,
+            });
+        }
+        for step in 0.. {
+            if step % self.ni == 0 {
+                let k = step / self.ni;
+                if inputs.iter().any(|messages| k >= messages.len()) {
+                    break;
+                }
+                for (feed, messages) in self.inputs.iter().zip(&inputs) {
+                    (feed.read)(&mut self.client, &messages[k])?;
+                }
+                self.client.update();
+                n_step += 1;
+            }
+            if (step + 1) % self.no == 0 && !distribute(&mut self.client, false)? {
+                break;
+            }
+        }
+        Ok(ReplayReport {
+            n_step,
+            outputs: reports,
+        })
+    }
+}
@@ -0,0 +1,476 @@
+/*!
+# Sub-systems
+
+A [SubSystem] is a named group of actors that is connected to the other actors of a [Model](crate::model::Model)
+as if it were a single actor.
+
+The actors are added to the sub-system with [SubSystem::add] that declares which inputs and outputs
+of an actor are the entries and the exits of the sub-system, by their data identifiers.
+The outputs of other actors are connected to the entries of the sub-system with `into_input`
+and the exits of the sub-system are connected to other actors with [SubSystem::add_output],
+the same way as for an [Actor](crate::Actor).
+A sub-system can itself be added to another sub-system, and its entries and exits declared
+as the entries and exits of the enclosing sub-system.
+
+In a model, a sub-system is replaced by its actors: the actors of the sub-system and the actors
+they are connected to exchange their data directly.
+The model [Graph](crate::model::Graph) draws the actors of a sub-system inside a cluster
+that can be collapsed into a single node with [Graph::collapse](crate::model::Graph::collapse).
+
+```
+# tokio_test::block_on(async {
+use gmt_dos_actors::prelude::*;
+use gmt_dos_clients::{interface::UID, Logging, Sampler, Signals};
+#[derive(UID)]
+enum In {};
+#[derive(UID)]
+enum Mid {};
+#[derive(UID)]
+enum Out {};
+
+let mut first: Actor<_> = Sampler::<Vec<f64>, In, Mid>::default().into();
+let mut second: Actor<_> = Sampler::<Vec<f64>, Mid, Out>::default().into();
+first.add_output().build::<Mid>().into_input(&mut second)?;
+let mut relay = SubSystem::new("Relay");
+relay.add(first).entry::<In>();
+relay.add(second).exit::<Out>();
+
+let mut source: Initiator<_> = Signals::new(1, 100).into();
+let logging = Logging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_>::new(logging.clone());
+source.add_output().build::<In>().into_input(&mut relay)?;
+relay.add_output().build::<Out>().into_input(&mut sink)?;
+
+let model = model!(source, relay, sink);
+println!("{}", model.graph().unwrap().as_mermaid());
+model.check()?.run().await?;
+assert_eq!(logging.lock().await.len(), 100);
+# Ok::<(), Box<dyn std::error::Error>>(())
+# });
+```
+*/
+
+use crate::{
+    actor::{Monitor, PlainActor},
+    interface::{Data, UniqueIdentifier},
+    model::{ActorReport, Control},
+    network::{InputPort, InputsRate, OutputPort, Overflow},
+    ActorOutputBuilder, Result, Task,
+};
+use async_trait::async_trait;
+use futures::future::join_all;
+use std::{
+    any::{type_name, Any},
+    fmt::{Debug, Display},
+    marker::PhantomData,
+    sync::Arc,
+};
+
+type Rx<U> = flume::Receiver<Data<U>>;
+// Connects a receiver to the input of an actor of the sub-system
+type AddPort<U> = fn(&mut dyn Any, Rx<U>, u64);
+// Adds an output to an actor of the sub-system
+type BuildPort<U> = fn(&mut dyn Any, ActorOutputBuilder) -> (u64, Vec<Rx<U>>);
+
+fn add_port<T, U>(actor: &mut dyn Any, rx: Rx<U>, hash: u64)
+where
+    T: 'static + InputPort<U>,
+    U: UniqueIdentifier,
+{
+    actor.downcast_mut::<T>().unwrap().add_port(rx, hash)
+}
+fn build_port<T, U>(actor: &mut dyn Any, builder: ActorOutputBuilder) -> (u64, Vec<Rx<U>>)
+where
+    T: 'static + OutputPort<U>,
+    U: UniqueIdentifier,
+{
+    actor.downcast_mut::<T>().unwrap().build_port(builder)
+}
+
+// Entry or exit of a sub-system
+struct Port {
+    // index of the actor in the sub-system
+    actor: usize,
+    connected: bool,
+    // either an `AddPort<U>` or a `BuildPort<U>` function
+    connect: Box<dyn Any + Send>,
+}
+
+/// Named group of actors with entries and exits
+pub struct SubSystem {
+    name: String,
+    subsystem: Vec<String>,
+    actors: Vec<Box<dyn Task>>,
+    entries: Vec<Port>,
+    exits: Vec<Port>,
+}
+impl SubSystem {
+    /// Creates a new empty sub-system
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            subsystem: Vec::new(),
+            actors: Vec::new(),
+            entries: Vec::new(),
+            exits: Vec::new(),
+        }
+    }
+    /// Adds an actor, or a sub-system, to the sub-system
+    ///
+    /// The entries and exits of the sub-system are declared with the returned [Ports]
+    pub fn add<T: 'static + Task>(&mut self, mut actor: T) -> Ports<'_, T> {
+        actor.enter_subsystem(&self.name);
+        self.actors.push(Box::new(actor));
+        Ports {
+            index: self.actors.len() - 1,
+            subsystem: self,
+            actor: PhantomData,
+        }
+    }
+    /// Adds a new output to one of the exits of the sub-system
+    pub fn add_output(&mut self) -> PortBuilder<'_> {
+        let name = self.name.clone();
+        PortBuilder::new(self, name)
+    }
+    /// Takes the actors out of the sub-system, replacing the nested sub-systems by their actors
+    fn take_actors(&mut self) -> Vec<Box<dyn Task>> {
+        flatten(std::mem::take(&mut self.actors))
+    }
+}
+
+/// Replaces the sub-systems by their actors
+pub(crate) fn flatten(actors: Vec<Box<dyn Task>>) -> Vec<Box<dyn Task>> {
+    actors
+        .into_iter()
+        .flat_map(
+            |mut actor| match actor.as_mut_any().downcast_mut::<SubSystem>() {
+                Some(subsystem) => subsystem.take_actors(),
+                None => vec![actor],
+            },
+        )
+        .collect()
+}
+
+/// Entries and exits of a [SubSystem] on one of its actors
+pub struct Ports<'a, T> {
+    subsystem: &'a mut SubSystem,
+    index: usize,
+    actor: PhantomData<T>,
+}
+impl<'a, T: 'static + Task> Ports<'a, T> {
+    /// Declares the input `U` of the actor as an entry of the sub-system
+    pub fn entry<U>(self) -> Self
+    where
+        T: InputPort<U>,
+        U: 'static + UniqueIdentifier,
+    {
+        self.subsystem.entries.push(Port {
+            actor: self.index,
+            connected: false,
+            connect: Box::new(add_port::<T, U> as AddPort<U>),
+        });
+        self
+    }
+    /// Declares the output `U` of the actor as an exit of the sub-system
+    pub fn exit<U>(self) -> Self
+    where
+        T: OutputPort<U>,
+        U: 'static + UniqueIdentifier,
+    {
+        self.subsystem.exits.push(Port {
+            actor: self.index,
+            connected: false,
+            connect: Box::new(build_port::<T, U> as BuildPort<U>),
+        });
+        self
+    }
+}
+
+impl<U: 'static + UniqueIdentifier> InputPort<U> for SubSystem {
+    /// Adds the input to the first actor with the entry `U` that is not connected yet
+    fn add_port(&mut self, rx: Rx<U>, hash: u64) {
+        let Some(port) = self
+            .entries
+            .iter_mut()
+            .find(|port| !port.connected && port.connect.is::<AddPort<U>>())
+        else {
+            panic!(
+                r#"no entry "{}" left in sub-system "{}""#,
+                type_name::<U>(),
+                self.name
+            )
+        };
+        port.connected = true;
+        let add_port = port.connect.downcast_ref::<AddPort<U>>().unwrap();
+        add_port(self.actors[port.actor].as_mut_any(), rx, hash)
+    }
+}
+impl<U: 'static + UniqueIdentifier> OutputPort<U> for SubSystem {
+    /// Adds the output to the first actor with the exit `U` that is not connected yet
+    fn build_port(&mut self, builder: ActorOutputBuilder) -> (u64, Vec<Rx<U>>) {
+        let Some(port) = self
+            .exits
+            .iter_mut()
+            .find(|port| !port.connected && port.connect.is::<BuildPort<U>>())
+        else {
+            panic!(
+                r#"no exit "{}" left in sub-system "{}""#,
+                type_name::<U>(),
+                self.name
+            )
+        };
+        port.connected = true;
+        let build_port = port.connect.downcast_ref::<BuildPort<U>>().unwrap();
+        build_port(self.actors[port.actor].as_mut_any(), builder)
+    }
+}
+impl<const NI: usize> InputsRate<NI> for SubSystem {}
+
+/// Output builder of a [SubSystem] or of a [DynActor](crate::DynActor)
+pub struct PortBuilder<'a, T = SubSystem> {
+    port: &'a mut T,
+    name: String,
+    builder: ActorOutputBuilder,
+}
+impl<'a, T> PortBuilder<'a, T> {
+    /// Creates a new output builder for `port` named `name`
+    pub(crate) fn new(port: &'a mut T, name: String) -> Self {
+        Self {
+            port,
+            name,
+            builder: ActorOutputBuilder::new(1),
+        }
+    }
+    /// Sets the channel to unbounded
+    pub fn unbounded(self) -> Self {
+        Self {
+            builder: self.builder.unbounded(),
+            ..self
+        }
+    }
+    /// Sets the capacity of the bounded channel (default: 1)
+    pub fn capacity(self, capacity: usize) -> Self {
+        Self {
+            builder: self.builder.capacity(capacity),
+            ..self
+        }
+    }
+    /// Sets the policy of the output when the channel is full (default: [Overflow::Block])
+    pub fn overflow(self, overflow: Overflow) -> Self {
+        Self {
+            builder: self.builder.overflow(overflow),
+            ..self
+        }
+    }
+    /// Flags the output to be bootstrapped
+    pub fn bootstrap(self) -> Self {
+        Self {
+            builder: self.builder.bootstrap(),
+            ..self
+        }
+    }
+    /// Multiplexes the output `n` times
+    pub fn multiplex(self, n: usize) -> Self {
+        Self {
+            builder: self.builder.multiplex(n),
+            ..self
+        }
+    }
+    /// Try to build a new output where you must fail to succeed
+    pub fn build<U>(self) -> std::result::Result<(), PortRx<U>>
+    where
+        T: OutputPort<U>,
+        U: 'static + UniqueIdentifier,
+    {
+        let (hash, rxs) = OutputPort::<U>::build_port(self.port, self.builder);
+        Err(PortRx {
+            name: self.name,
+            hash,
+            rxs,
+        })
+    }
+}
+
+/// Receivers of the output of a [SubSystem] or of a [DynActor](crate::DynActor)
+pub struct PortRx<U: UniqueIdentifier> {
+    name: String,
+    hash: u64,
+    rxs: Vec<Rx<U>>,
+}
+
+/// Assign the outputs of a [SubSystem] to the inputs of actors or sub-systems
+///
+/// The rates of the actors at both ends of the connections are checked when the [Model](crate::model::Model) is checked
+pub trait TryIntoPorts<U: UniqueIdentifier> {
+    /// Try to create a new input for 'actor' from the last 'Receiver'
+    fn into_input<A>(self, actor: &mut A) -> Self
+    where
+        A: InputPort<U>,
+        Self: Sized;
+}
+impl<U: UniqueIdentifier> TryIntoPorts<U> for std::result::Result<(), PortRx<U>> {
+    fn into_input<A>(mut self, actor: &mut A) -> Self
+    where
+        A: InputPort<U>,
+    {
+        let Err(PortRx {
+            hash, ref mut rxs, ..
+        }) = self
+        else {
+            panic!(r#"Input receivers have been exhausted"#)
+        };
+        let Some(recv) = rxs.pop() else {
+            panic!(r#"Input receivers is empty"#)
+        };
+        actor.add_port(recv, hash);
+        if rxs.is_empty() {
+            Ok(())
+        } else {
+            self
+        }
+    }
+}
+impl<U: UniqueIdentifier> PortRx<U> {
+    /// Returns the output hash and the receivers
+    #[cfg(feature = "loader")]
+    pub(crate) fn into_parts(self) -> (u64, Vec<Rx<U>>) {
+        (self.hash, self.rxs)
+    }
+}
+impl<U: UniqueIdentifier> std::error::Error for PortRx<U> {}
+impl<U: UniqueIdentifier> Display for PortRx<U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            r#"TryIntoPorts for output "{}" of "{}", check output multiplex #"#,
+            type_name::<U>(),
+            self.name
+        )
+    }
+}
+impl<U: UniqueIdentifier> Debug for PortRx<U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        <Self as Display>::fmt(self, f)
+    }
+}
+
+impl Display for SubSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} [{}]", self.name.to_uppercase(), self.actors.len())?;
+        for actor in &self.actors {
+            write!(f, " {}", actor)?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Task for SubSystem {
+    /// Runs the loops of all the actors of the sub-system
+    async fn async_run(&mut self) -> Result<()> {
+        join_all(self.actors.iter_mut().map(|actor| actor.async_run()))
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>>>()?;
+        Ok(())
+    }
+    fn spawn(mut self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            self.task().await;
+        })
+    }
+    fn check_inputs(&self) -> Result<()> {
+        self.actors
+            .iter()
+            .try_for_each(|actor| actor.check_inputs())
+    }
+    fn check_outputs(&self) -> Result<()> {
+        self.actors
+            .iter()
+            .try_for_each(|actor| actor.check_outputs())
+    }
+    /// Runs the actors of the sub-system
+    ///
+    /// Returns the report of the first actor that exits abnormally, if any
+    async fn task(&mut self) -> ActorReport {
+        let reports = join_all(self.actors.iter_mut().map(|actor| actor.task())).await;
+        let n_step = reports
+            .iter()
+            .map(|report| report.n_step)
+            .max()
+            .unwrap_or_default();
+        reports
+            .into_iter()
+            .find(|report| !report.exit.is_normal())
+            .unwrap_or_else(|| ActorReport::new(self.name.clone(), n_step, Ok(())))
+    }
+    fn n_inputs(&self) -> usize {
+        self.actors.iter().map(|actor| actor.n_inputs()).sum()
+    }
+    fn n_outputs(&self) -> usize {
+        self.actors.iter().map(|actor| actor.n_outputs()).sum()
+    }
+    fn inputs_hashes(&self) -> Vec<u64> {
+        self.actors
+            .iter()
+            .flat_map(|actor| actor.inputs_hashes())
+            .collect()
+    }
+    fn outputs_hashes(&self) -> Vec<u64> {
+        self.actors
+            .iter()
+            .flat_map(|actor| actor.outputs_hashes())
+            .collect()
+    }
+    /// Returns the sub-system collapsed into a single actor
+    fn as_plain(&self) -> PlainActor {
+        PlainActor::collapse(
+            self.name.clone(),
+            self.subsystem.clone(),
+            self.actors.iter().map(|actor| actor.as_plain()).collect(),
+            &[],
+        )
+    }
+    fn set_control(&mut self, control: Control) {
+        self.actors
+            .iter_mut()
+            .for_each(|actor| actor.set_control(control.clone()))
+    }
+    fn set_sampling_frequency(&mut self, sampling_frequency: f64) {
+        self.actors
+            .iter_mut()
+            .for_each(|actor| actor.set_sampling_frequency(sampling_frequency))
+    }
+    fn monitor(&self) -> Arc<Monitor> {
+        Default::default()
+    }
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+    fn enter_subsystem(&mut self, name: &str) {
+        self.subsystem.insert(0, name.to_string());
+        self.actors
+            .iter_mut()
+            .for_each(|actor| actor.enter_subsystem(name));
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+    #[cfg(feature = "checkpoint")]
+    fn client_state(&self) -> Option<crate::model::ClientState> {
+        None
+    }
+    #[cfg(feature = "checkpoint")]
+    fn input_queues(&self) -> Vec<Box<dyn crate::model::InputQueue>> {
+        Vec::new()
+    }
+    #[cfg(feature = "checkpoint")]
+    fn restore(
+        &mut self,
+        _snapshot: &crate::model::ActorSnapshot,
+    ) -> std::result::Result<(), crate::model::CheckpointError> {
+        Err(crate::model::CheckpointError::NotCheckpointed(
+            self.name.clone(),
+        ))
+    }
+}
@@ -0,0 +1,61 @@
+/*!
+# Actors execution timeline
+
+With the `tracing` feature, the actors loop emits the following [tracing] spans:
+ * `collect`: an actor waiting on and reading all its inputs,
+ * `recv`: an actor waiting on and reading one input,
+ * `update`: the update of an actor client,
+ * `distribute`: an actor writing and sending all its outputs,
+ * `send`: an actor writing and sending one output.
+
+The spans of the actors loop are recorded with the [chrome] subscriber in the Chrome trace-event JSON format.
+The file can be opened with [Perfetto](https://ui.perfetto.dev) or with `chrome://tracing`.
+
+# Example
+
+```no_run
+# tokio_test::block_on(async {
+use gmt_dos_actors::prelude::*;
+# use gmt_dos_clients::{interface::UID, Logging, Sampler, Signals};
+# let mut source: Initiator<_> = Signals::new(1, 100).into();
+# #[derive(UID)]
+# enum Source {};
+# let mut sampler: Actor<_> = Sampler::<Vec<f64>, Source>::default().into();
+# let logging = Logging::<f64>::default().into_arcx();
+# let mut sink = Terminator::<_>::new(logging.clone());
+# source.add_output().build::<Source>().into_input(&mut sampler);
+# sampler.add_output().build::<Source>().into_input(&mut sink);
+let _guard = gmt_dos_actors::trace::chrome("model-trace.json")?;
+Model::new(vec![Box::new(source), Box::new(sampler), Box::new(sink)])
+       .check()?
+       .run()
+       .await?;
+# Ok::<(), Box<dyn std::error::Error>>(())
+# });
+```
+The trace is written to file when the guard is dropped.
+*/
+
+use std::path::Path;
+
+pub use tracing_chrome::FlushGuard;
+use tracing_chrome::{ChromeLayerBuilder, TraceStyle};
+use tracing_subscriber::prelude::*;
+pub use tracing_subscriber::util::TryInitError;
+
+/// Sets a global subscriber that writes the spans to the Chrome trace `path`
+///
+/// The spans are written as asynchronous events, so the spans of the actors tasks
+/// that are interleaved on the same thread are not nested into each other.
+/// The trace file is completed when the returned [FlushGuard] is dropped.
+///
+/// Returns an error if a global subscriber has already been set
+pub fn chrome<P: AsRef<Path>>(path: P) -> Result<FlushGuard, TryInitError> {
+    let (chrome_layer, guard) = ChromeLayerBuilder::new()
+        .file(path.as_ref())
+        .include_args(true)
+        .trace_style(TraceStyle::Async)
+        .build();
+    tracing_subscriber::registry().with(chrome_layer).try_init()?;
+    Ok(guard)
+}
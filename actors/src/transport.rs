@@ -0,0 +1,741 @@
+/*!
+# Actors network transport
+
+The transport layer links an actor output in one [model](crate::model) to an actor input
+in another model, running in another process or on another machine,
+over a TCP or a Unix socket [Endpoint].
+
+On the sending side, the output is connected to a [Transmitter]:
+```ignore
+producer
+    .add_output()
+    .build::<U>()
+    .into_transmitter(&mut transmitter);
+```
+and, on the receiving side, a [Receiver] is connected to the input:
+```ignore
+receiver.into_input(&mut consumer)?;
+```
+Both the [Transmitter] and the [Receiver] are part of their respective models, alongside the other actors.
+The [Receiver] listens on the endpoint and the [Transmitter] connects to it,
+retrying until the [Receiver] is listening or until the transmitter [timeout](Transmitter::timeout) has elapsed.
+
+The data is serialized with [bincode](https://docs.rs/bincode), so the data type of the UID must implement
+[serde](https://docs.rs/serde)'s `Serialize` and `Deserialize` traits.
+When the link is set up, the UID full name, module path included, and the rate of the transmitted output are checked against
+the UID name and the inputs rate of the receiving actors, the inputs fed by a [Receiver] sharing the same rate.
+The bootstrapped data of the output goes through the link like any other data,
+and the link ends when either the output or the input is dropped.
+The [Transmitter] tells the [Receiver] that its output has been dropped,
+and a link closed without it ends the [Receiver] as a dropped input.
+The frames are limited in size, see [Receiver::max_frame_size], and the header frame of the [Transmitter]
+is limited to a few kilobytes, so a foreign client cannot make the [Receiver] allocate more.
+
+```
+# tokio_test::block_on(async {
+use gmt_dos_actors::{prelude::*, transport::{Endpoint, IntoTransmitter, Receiver, Transmitter}};
+use gmt_dos_clients::{interface::UID, Logging, Signals};
+#[derive(UID)]
+enum Source {};
+let endpoint = Endpoint::unix(std::env::temp_dir().join(format!("source-{}.sock", std::process::id())));
+
+// receiving model
+let mut receiver = Receiver::<Source>::new(endpoint.clone());
+let logging = Logging::<f64>::default().into_arcx();
+let mut sink = Terminator::<_>::new(logging.clone());
+receiver.into_input(&mut sink)?;
+let receiving = model!(receiver, sink).check()?.run();
+
+// sending model
+let mut source: Initiator<_> = Signals::new(1, 100).into();
+let mut transmitter = Transmitter::<Source>::new(endpoint);
+source
+    .add_output()
+    .build::<Source>()
+    .into_transmitter(&mut transmitter);
+model!(source, transmitter).check()?.run().await?;
+
+receiving.await?;
+assert_eq!(logging.lock().await.len(), 100);
+# Ok::<(), Box<dyn std::error::Error>>(())
+# });
+```
+
+The transport endpoints must be run with [Model::run](crate::model::Model::run),
+they cannot be run with [Model::run_lockstep](crate::model::Model::run_lockstep).
+*/
+
+use crate::{
+    actor::{
+        plain::{IOData, IO},
+        Fill, Gauge, Monitor, PlainActor, Wait,
+    },
+    model::{ActorReport, Control},
+    network::OutputRx,
+    uid::{uid_name, uid_short_name},
+    Actor, ActorError, Assoc, Data, Read, Task, UniqueIdentifier, Update,
+};
+use async_trait::async_trait;
+use bincode::Options;
+use futures::future::join_all;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    any::{type_name, Any},
+    collections::hash_map::DefaultHasher,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    io,
+    marker::PhantomData,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+#[derive(Debug, thiserror::Error)]
+pub enum TransportError {
+    #[error("failed to connect to {0}")]
+    Connect(Endpoint, #[source] io::Error),
+    #[error("failed to listen on {0}")]
+    Listen(Endpoint, #[source] io::Error),
+    #[error("transport I/O error")]
+    Io(#[from] io::Error),
+    #[error("failed to serialize or to deserialize the data")]
+    Bincode(#[from] bincode::Error),
+    #[error("the link was closed before receiving the transmitter header")]
+    NoHeader,
+    #[error(r#"expected "{expected}" data, the transmitter sends "{found}""#)]
+    Uid { expected: String, found: String },
+    #[error("the inputs rate ({inputs_rate}) does not match the transmitted outputs rate ({outputs_rate})")]
+    Rate {
+        inputs_rate: usize,
+        outputs_rate: usize,
+    },
+    #[error("the input rate ({input_rate}) differs from the rate ({inputs_rate}) of the other inputs of the receiver")]
+    InputRate {
+        inputs_rate: usize,
+        input_rate: usize,
+    },
+    #[error("the frame of {size} bytes exceeds the maximum frame size of {max_size} bytes")]
+    FrameSize { size: u64, max_size: u64 },
+}
+type Result<T> = std::result::Result<T, TransportError>;
+
+/// Network address of a link
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    /// TCP socket address, e.g. `127.0.0.1:5000`
+    Tcp(String),
+    /// Unix socket path
+    #[cfg(unix)]
+    Unix(std::path::PathBuf),
+}
+impl Endpoint {
+    /// Creates a TCP endpoint
+    pub fn tcp<S: Into<String>>(address: S) -> Self {
+        Self::Tcp(address.into())
+    }
+    /// Creates a Unix socket endpoint
+    #[cfg(unix)]
+    pub fn unix<P: Into<std::path::PathBuf>>(path: P) -> Self {
+        Self::Unix(path.into())
+    }
+    /// Connects to the endpoint
+    async fn connect(&self) -> io::Result<Box<dyn Stream>> {
+        Ok(match self {
+            Endpoint::Tcp(address) => {
+                let stream = tokio::net::TcpStream::connect(address).await?;
+                stream.set_nodelay(true)?;
+                Box::new(stream)
+            }
+            #[cfg(unix)]
+            Endpoint::Unix(path) => Box::new(tokio::net::UnixStream::connect(path).await?),
+        })
+    }
+    /// Listens on the endpoint and accepts the first connection
+    async fn accept(&self) -> io::Result<Box<dyn Stream>> {
+        Ok(match self {
+            Endpoint::Tcp(address) => {
+                let listener = tokio::net::TcpListener::bind(address).await?;
+                let (stream, _) = listener.accept().await?;
+                stream.set_nodelay(true)?;
+                Box::new(stream)
+            }
+            #[cfg(unix)]
+            Endpoint::Unix(path) => {
+                // removes the socket left over by a previous run
+                if path.exists() {
+                    std::fs::remove_file(path)?;
+                }
+                let listener = tokio::net::UnixListener::bind(path)?;
+                let (stream, _) = listener.accept().await?;
+                std::fs::remove_file(path)?;
+                Box::new(stream)
+            }
+        })
+    }
+}
+impl Display for Endpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Endpoint::Tcp(address) => write!(f, "tcp://{address}"),
+            #[cfg(unix)]
+            Endpoint::Unix(path) => write!(f, "unix://{}", path.display()),
+        }
+    }
+}
+
+trait Stream: AsyncRead + AsyncWrite + Unpin + Send {}
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Stream for T {}
+
+/// Maximum size of the [Header] frame
+const MAX_HEADER_SIZE: u64 = 1 << 12;
+/// Default maximum size of the data frames
+const MAX_FRAME_SIZE: u64 = 1 << 28;
+
+/// Writes a length prefixed frame
+async fn write_frame<T: Serialize>(stream: &mut Box<dyn Stream>, value: &T) -> Result<()> {
+    let bytes = bincode::serialize(value)?;
+    stream.write_u64_le(bytes.len() as u64).await?;
+    stream.write_all(&bytes).await?;
+    stream.flush().await?;
+    Ok(())
+}
+/// Reads a length prefixed frame of at most `max_size` bytes, returns `None` if the link has been closed
+///
+/// The frame is deserialized with the same encoding as [bincode::serialize],
+/// reading no more bytes than the frame size
+async fn read_frame<T: DeserializeOwned>(
+    stream: &mut Box<dyn Stream>,
+    max_size: u64,
+) -> Result<Option<T>> {
+    let size = match stream.read_u64_le().await {
+        Ok(size) => size,
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    if size > max_size {
+        return Err(TransportError::FrameSize { size, max_size });
+    }
+    let mut bytes = vec![0u8; size as usize];
+    stream.read_exact(&mut bytes).await?;
+    Ok(Some(
+        bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .allow_trailing_bytes()
+            .with_limit(size)
+            .deserialize(&bytes)?,
+    ))
+}
+
+/// First frame sent by a [Transmitter]
+#[derive(Debug, Serialize, Deserialize)]
+struct Header {
+    uid: String,
+    rate: usize,
+}
+
+/// Frames sent by a [Transmitter] after the [Header]
+#[derive(Debug, Serialize, Deserialize)]
+enum Frame<T> {
+    Data(T),
+    /// The output connected to the transmitter has been dropped
+    End,
+}
+
+/// Sending end of a link
+///
+/// A [Transmitter] receives the data of an actor output and sends it to the [Receiver] listening on the [Endpoint]
+pub struct Transmitter<U: UniqueIdentifier> {
+    endpoint: Endpoint,
+    rx: Option<flume::Receiver<Data<U>>>,
+    hash: u64,
+    rate: usize,
+    timeout: Duration,
+    monitor: Arc<Monitor>,
+    subsystem: Vec<String>,
+}
+impl<U: UniqueIdentifier> Transmitter<U> {
+    /// Creates a new transmitter to the given endpoint
+    pub fn new(endpoint: Endpoint) -> Self {
+        Self {
+            endpoint,
+            rx: None,
+            hash: 0,
+            rate: 0,
+            timeout: Duration::from_secs(10),
+            monitor: Default::default(),
+            subsystem: Vec::new(),
+        }
+    }
+    /// Sets the time the transmitter waits for the [Receiver] to listen on the endpoint (default: 10s)
+    pub fn timeout(self, timeout: Duration) -> Self {
+        Self { timeout, ..self }
+    }
+    fn name(&self) -> String {
+        format!("{} transmitter to {}", uid_short_name::<U>(), self.endpoint)
+    }
+    /// Connects to the endpoint, retrying until the timeout has elapsed
+    async fn connect(&self) -> Result<Box<dyn Stream>> {
+        let start = Instant::now();
+        loop {
+            match self.endpoint.connect().await {
+                Ok(stream) => return Ok(stream),
+                Err(e) if start.elapsed() > self.timeout => {
+                    return Err(TransportError::Connect(self.endpoint.clone(), e))
+                }
+                Err(_) => tokio::time::sleep(Duration::from_millis(100)).await,
+            }
+        }
+    }
+}
+impl<U> Transmitter<U>
+where
+    U: 'static + UniqueIdentifier,
+    Assoc<U>: Send + Sync + Serialize,
+{
+    async fn run(&mut self) -> std::result::Result<(), ActorError> {
+        let rx = self.rx.as_ref().ok_or(ActorError::NoInputs)?;
+        let mut stream = self.connect().await?;
+        let header = Header {
+            uid: uid_name::<U>(),
+            rate: self.rate,
+        };
+        write_frame(&mut stream, &header).await?;
+        let wait = Wait::Recv {
+            uid: type_name::<U>().to_string(),
+            hash: self.hash,
+            fill: Fill(rx.len(), rx.capacity()),
+        };
+        let gauge = {
+            let rx = rx.clone();
+            Gauge::new(move || vec![Fill(rx.len(), rx.capacity())])
+        };
+        let channel = self.monitor.register(wait, gauge);
+        loop {
+            self.monitor.wait_on(&channel);
+            let data = rx.recv_async().await;
+            self.monitor.done(&channel);
+            let data = match data {
+                Ok(data) => data,
+                Err(source) => {
+                    // the receiver is told that the output has been dropped, unless it has already ended
+                    write_frame(&mut stream, &Frame::<&Data<U>>::End).await.ok();
+                    return Err(ActorError::DropRecv {
+                        msg: type_name::<U>().to_string(),
+                        source,
+                    });
+                }
+            };
+            self.monitor.received(1);
+            match write_frame(&mut stream, &Frame::Data(&data)).await {
+                Ok(_) => (),
+                // the receiving end of the link has been dropped
+                Err(TransportError::Io(e))
+                    if matches!(
+                        e.kind(),
+                        io::ErrorKind::BrokenPipe | io::ErrorKind::ConnectionReset
+                    ) =>
+                {
+                    return Err(ActorError::DropSend {
+                        msg: type_name::<U>().to_string(),
+                        source: flume::SendError(()),
+                    })
+                }
+                Err(e) => return Err(e.into()),
+            }
+            self.monitor.step();
+        }
+    }
+}
+impl<U: UniqueIdentifier> Display for Transmitter<U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}:", self.name().to_uppercase())?;
+        writeln!(f, " - inputs  #1:")?;
+        writeln!(f, "   1. {}", type_name::<U>())
+    }
+}
+
+/// Connects an actor output to a [Transmitter]
+pub trait IntoTransmitter<U: UniqueIdentifier> {
+    /// Creates the transmitter input from the last receiver of the output
+    fn into_transmitter(self, transmitter: &mut Transmitter<U>) -> Self;
+}
+impl<U, CO, const NO: usize, const NI: usize> IntoTransmitter<U>
+    for std::result::Result<(), OutputRx<U, CO, NI, NO>>
+where
+    U: 'static + Send + Sync + UniqueIdentifier,
+    CO: 'static + Update + Send + crate::interface::Write<U>,
+{
+    fn into_transmitter(mut self, transmitter: &mut Transmitter<U>) -> Self {
+        let Err(output_rx) = &mut self else {
+            panic!(r#"Input receivers have been exhausted"#)
+        };
+        let Some((hash, rx)) = output_rx.pop() else {
+            panic!(r#"Input receivers is empty"#)
+        };
+        transmitter.rx = Some(rx);
+        transmitter.hash = hash;
+        transmitter.rate = NO;
+        if output_rx.is_empty() {
+            Ok(())
+        } else {
+            self
+        }
+    }
+}
+
+/// Receiving end of a link
+///
+/// A [Receiver] listens on the [Endpoint] for a [Transmitter] and sends the data it receives to actor inputs
+pub struct Receiver<U: UniqueIdentifier> {
+    endpoint: Endpoint,
+    txs: Vec<flume::Sender<Data<U>>>,
+    hash: u64,
+    rate: usize,
+    max_frame_size: u64,
+    control: Option<Control>,
+    monitor: Arc<Monitor>,
+    subsystem: Vec<String>,
+    uid: PhantomData<U>,
+}
+impl<U: UniqueIdentifier> Receiver<U> {
+    /// Creates a new receiver listening on the given endpoint
+    pub fn new(endpoint: Endpoint) -> Self {
+        let mut hasher = DefaultHasher::new();
+        endpoint.hash(&mut hasher);
+        uid_name::<U>().hash(&mut hasher);
+        Self {
+            endpoint,
+            txs: Vec::new(),
+            hash: hasher.finish(),
+            rate: 0,
+            max_frame_size: MAX_FRAME_SIZE,
+            control: None,
+            monitor: Default::default(),
+            subsystem: Vec::new(),
+            uid: PhantomData,
+        }
+    }
+    /// Sets the maximum size in bytes of the data frames (default: 256MiB)
+    ///
+    /// The receiver ends with [TransportError::FrameSize] if a larger frame is sent
+    pub fn max_frame_size(self, max_frame_size: u64) -> Self {
+        Self {
+            max_frame_size,
+            ..self
+        }
+    }
+    fn name(&self) -> String {
+        format!("{} receiver on {}", uid_short_name::<U>(), self.endpoint)
+    }
+    /// Creates a new input for `actor` fed by the receiver
+    ///
+    /// An error is returned if the inputs rate of `actor` differs from the rate of the other inputs of the receiver
+    pub fn into_input<CI, const NI: usize, const N: usize>(
+        &mut self,
+        actor: &mut Actor<CI, NI, N>,
+    ) -> Result<&mut Self>
+    where
+        CI: 'static + Update + Send + Read<U>,
+        U: 'static + Send + Sync,
+        Assoc<U>: Send + Sync,
+    {
+        if !self.txs.is_empty() && self.rate != NI {
+            return Err(TransportError::InputRate {
+                inputs_rate: self.rate,
+                input_rate: NI,
+            });
+        }
+        let (tx, rx) = flume::bounded(1);
+        self.txs.push(tx);
+        self.rate = NI;
+        actor.add_input(rx, self.hash);
+        Ok(self)
+    }
+}
+impl<U> Receiver<U>
+where
+    U: 'static + UniqueIdentifier,
+    Assoc<U>: Send + Sync + DeserializeOwned,
+{
+    async fn run(&mut self) -> std::result::Result<(), ActorError> {
+        if self.txs.is_empty() {
+            return Err(ActorError::NoOutputs);
+        }
+        let mut stream = self
+            .endpoint
+            .accept()
+            .await
+            .map_err(|e| TransportError::Listen(self.endpoint.clone(), e))?;
+        let header: Header = read_frame(&mut stream, MAX_HEADER_SIZE)
+            .await?
+            .ok_or(TransportError::NoHeader)?;
+        if header.uid != uid_name::<U>() {
+            return Err(TransportError::Uid {
+                expected: uid_name::<U>(),
+                found: header.uid,
+            }
+            .into());
+        }
+        if header.rate != self.rate {
+            return Err(TransportError::Rate {
+                inputs_rate: self.rate,
+                outputs_rate: header.rate,
+            }
+            .into());
+        }
+        let wait = Wait::Send {
+            uid: type_name::<U>().to_string(),
+            hash: self.hash,
+            fill: self
+                .txs
+                .iter()
+                .map(|tx| Fill(tx.len(), tx.capacity()))
+                .collect(),
+        };
+        let gauge = {
+            let txs = self.txs.clone();
+            Gauge::new(move || {
+                txs.iter()
+                    .map(|tx| Fill(tx.len(), tx.capacity()))
+                    .collect()
+            })
+        };
+        let channel = self.monitor.register(wait, gauge);
+        loop {
+            if let Some(control) = self.control.as_mut() {
+                control.proceed(&self.monitor, self.rate).await?;
+            }
+            // the end frame is sent once the output of the transmitter is dropped,
+            // a link closed without it is a transmitter that has ended abnormally
+            let data: Data<U> = match read_frame(&mut stream, self.max_frame_size).await? {
+                Some(Frame::Data(data)) => data,
+                Some(Frame::End) => return Err(ActorError::NoData),
+                None => {
+                    return Err(ActorError::DropRecv {
+                        msg: type_name::<U>().to_string(),
+                        source: flume::RecvError::Disconnected,
+                    })
+                }
+            };
+            self.monitor.step();
+            self.monitor.wait_on(&channel);
+            let results = join_all(self.txs.iter().map(|tx| tx.send_async(data.clone()))).await;
+            self.monitor.done(&channel);
+            results
+                .into_iter()
+                .collect::<std::result::Result<Vec<()>, _>>()
+                .map_err(|_| ActorError::DropSend {
+                    msg: type_name::<U>().to_string(),
+                    source: flume::SendError(()),
+                })?;
+            self.monitor.sent(self.txs.len());
+        }
+    }
+}
+impl<U: UniqueIdentifier> Display for Receiver<U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}:", self.name().to_uppercase())?;
+        writeln!(f, " - outputs #1:")?;
+        writeln!(f, "   1. {} (#{})", type_name::<U>(), self.txs.len())
+    }
+}
+
+/// Runs a link endpoint and reports how it ended
+async fn report<F>(name: String, monitor: &Monitor, run: F) -> ActorReport
+where
+    F: std::future::Future<Output = std::result::Result<(), ActorError>>,
+{
+    crate::print_info(
+        format!("{} loop started", name),
+        None::<&dyn std::error::Error>,
+    );
+    ActorReport::catch(name.clone(), monitor, async {
+        let result = run.await;
+        if let Err(e) = result.as_ref() {
+            crate::print_info(format!("{} loop ended", name), Some(e));
+        }
+        let mut report = ActorReport::new(name.clone(), monitor.n_step(), result);
+        report.profile = monitor.profile();
+        report
+    })
+    .await
+}
+
+#[async_trait]
+impl<U> Task for Transmitter<U>
+where
+    U: 'static + Send + Sync + UniqueIdentifier,
+    Assoc<U>: Send + Sync + Serialize,
+{
+    async fn async_run(&mut self) -> crate::Result<()> {
+        self.run().await
+    }
+    fn spawn(mut self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            self.task().await;
+        })
+    }
+    fn check_inputs(&self) -> crate::Result<()> {
+        self.rx.as_ref().map(|_| ()).ok_or(ActorError::NoInputs)
+    }
+    fn check_outputs(&self) -> crate::Result<()> {
+        Ok(())
+    }
+    async fn task(&mut self) -> ActorReport {
+        let (name, monitor) = (self.name(), Arc::clone(&self.monitor));
+        report(name, &monitor, self.run()).await
+    }
+    fn n_inputs(&self) -> usize {
+        self.rx.as_ref().map_or(0, |_| 1)
+    }
+    fn n_outputs(&self) -> usize {
+        0
+    }
+    fn inputs_hashes(&self) -> Vec<u64> {
+        self.rx.as_ref().map_or(Vec::new(), |_| vec![self.hash])
+    }
+    fn outputs_hashes(&self) -> Vec<u64> {
+        Vec::new()
+    }
+    fn as_plain(&self) -> PlainActor {
+        PlainActor {
+            client: self.name(),
+            inputs_rate: self.rate,
+            outputs_rate: 0,
+            inputs: self.rx.as_ref().map(|rx| {
+                let io = IOData::new(type_name::<U>().to_string(), self.hash);
+                vec![match rx.capacity() {
+                    Some(_) => IO::Regular(io),
+                    None => IO::Unbounded(io),
+                }]
+            }),
+            outputs: None,
+            hash: 0,
+            image: None,
+            subsystem: self.subsystem.clone(),
+        }
+    }
+    fn set_control(&mut self, _control: Control) {}
+    fn set_sampling_frequency(&mut self, _sampling_frequency: f64) {}
+    fn monitor(&self) -> Arc<Monitor> {
+        Arc::clone(&self.monitor)
+    }
+    fn name(&self) -> String {
+        Transmitter::name(self)
+    }
+    fn enter_subsystem(&mut self, name: &str) {
+        self.subsystem.insert(0, name.to_string());
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+    #[cfg(feature = "checkpoint")]
+    fn client_state(&self) -> Option<crate::model::ClientState> {
+        None
+    }
+    #[cfg(feature = "checkpoint")]
+    fn input_queues(&self) -> Vec<Box<dyn crate::model::InputQueue>> {
+        Vec::new()
+    }
+    #[cfg(feature = "checkpoint")]
+    fn restore(
+        &mut self,
+        snapshot: &crate::model::ActorSnapshot,
+    ) -> std::result::Result<(), crate::model::CheckpointError> {
+        self.monitor.set_step(snapshot.n_step);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<U> Task for Receiver<U>
+where
+    U: 'static + Send + Sync + UniqueIdentifier,
+    Assoc<U>: Send + Sync + DeserializeOwned,
+{
+    async fn async_run(&mut self) -> crate::Result<()> {
+        self.run().await
+    }
+    fn spawn(mut self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            self.task().await;
+        })
+    }
+    fn check_inputs(&self) -> crate::Result<()> {
+        Ok(())
+    }
+    fn check_outputs(&self) -> crate::Result<()> {
+        if self.txs.is_empty() {
+            Err(ActorError::NoOutputs)
+        } else {
+            Ok(())
+        }
+    }
+    async fn task(&mut self) -> ActorReport {
+        let (name, monitor) = (self.name(), Arc::clone(&self.monitor));
+        report(name, &monitor, self.run()).await
+    }
+    fn n_inputs(&self) -> usize {
+        0
+    }
+    fn n_outputs(&self) -> usize {
+        self.txs.len()
+    }
+    fn inputs_hashes(&self) -> Vec<u64> {
+        Vec::new()
+    }
+    fn outputs_hashes(&self) -> Vec<u64> {
+        vec![self.hash; self.txs.len()]
+    }
+    fn as_plain(&self) -> PlainActor {
+        PlainActor {
+            client: self.name(),
+            inputs_rate: 0,
+            outputs_rate: self.rate,
+            inputs: None,
+            outputs: (!self.txs.is_empty()).then(|| {
+                vec![IO::Regular(IOData::new(
+                    type_name::<U>().to_string(),
+                    self.hash,
+                ))]
+            }),
+            hash: 0,
+            image: None,
+            subsystem: self.subsystem.clone(),
+        }
+    }
+    fn set_control(&mut self, control: Control) {
+        self.control = Some(control);
+    }
+    /// The data is forwarded with the stamps of the remote model
+    fn set_sampling_frequency(&mut self, _sampling_frequency: f64) {}
+    fn monitor(&self) -> Arc<Monitor> {
+        Arc::clone(&self.monitor)
+    }
+    fn name(&self) -> String {
+        Receiver::name(self)
+    }
+    fn enter_subsystem(&mut self, name: &str) {
+        self.subsystem.insert(0, name.to_string());
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+    #[cfg(feature = "checkpoint")]
+    fn client_state(&self) -> Option<crate::model::ClientState> {
+        None
+    }
+    #[cfg(feature = "checkpoint")]
+    fn input_queues(&self) -> Vec<Box<dyn crate::model::InputQueue>> {
+        Vec::new()
+    }
+    #[cfg(feature = "checkpoint")]
+    fn restore(
+        &mut self,
+        snapshot: &crate::model::ActorSnapshot,
+    ) -> std::result::Result<(), crate::model::CheckpointError> {
+        self.monitor.set_step(snapshot.n_step);
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+//! Names of the UIDs in descriptions, recordings, links and flowcharts
+
+#[cfg(any(feature = "loader", feature = "replay", feature = "transport"))]
+use std::any::type_name;
+
+/// Full name of the UID `U`
+///
+/// The name identifies the data of an output together with the output hash
+#[cfg(any(feature = "replay", feature = "transport"))]
+pub(crate) fn uid_name<U>() -> String {
+    type_name::<U>().to_string()
+}
+
+/// Short name of the UID `U`, without the module paths of the UID and of its type parameters
+///
+/// e.g. `Foo<Bar>` for `a::Foo<b::Bar>`
+#[cfg(any(feature = "loader", feature = "transport"))]
+pub(crate) fn uid_short_name<U>() -> String {
+    short_name(type_name::<U>())
+}
+
+/// Short name of the UID with the full name `name`, see [uid_short_name]
+pub(crate) fn short_name(name: &str) -> String {
+    name.split_inclusive(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+        .map(|path| path.rsplit("::").next().unwrap())
+        .collect()
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "checkpoint")]
+
+mod common;
+
+use common::{Command, Feedback, Logs, Setpoint, State};
+use gmt_dos_actors::model::{Model, Unknown};
+
+// The bootstrapped command of the controller is still in flight at the checkpoint
+fn feedback_model() -> anyhow::Result<(Model<Unknown>, Logs)> {
+    let Feedback {
+        setpoint,
+        controller,
+        plant,
+        logger,
+        logging,
+    } = Feedback::new(100);
+    let mut feedback = Feedback {
+        setpoint: setpoint.checkpoint(),
+        controller: controller.checkpoint(),
+        plant: plant.checkpoint(),
+        logger: logger.checkpoint(),
+        logging,
+    }
+    .connect()?;
+    feedback
+        .controller
+        .checkpoint_input::<Setpoint>()?
+        .checkpoint_input::<State>()?;
+    feedback.plant.checkpoint_input::<Command>()?;
+    feedback.logger.checkpoint_input::<State>()?;
+    Ok(feedback.into_model())
+}
+
+async fn data(logging: Logs) -> Vec<f64> {
+    logging.lock().await.iter().copied().collect()
+}
+
+#[tokio::test]
+async fn checkpointed_model_resumes() -> anyhow::Result<()> {
+    let (model, logging) = feedback_model()?;
+    model.check()?.run().await?;
+    let reference = data(logging).await;
+    // the plant is stepped once more with the bootstrapped command
+    assert_eq!(reference.len(), 101);
+
+    let (model, logging) = feedback_model()?;
+    let model = model.check()?.run();
+    let snapshot = model.checkpoint(40).await?;
+    assert!(snapshot.actors.iter().any(|actor| !actor.queued.is_empty()));
+    model.handle().resume();
+    model.await?;
+    assert_eq!(data(logging).await, reference);
+
+    let (model, _) = feedback_model()?;
+    let model = model.check()?.run();
+    let snapshot = model.checkpoint(40).await?;
+    model.handle().stop();
+    model.await?;
+    let (model, logging) = feedback_model()?;
+    model.resume(&snapshot)?.check()?.run().await?;
+    assert_eq!(data(logging).await, reference);
+    Ok(())
+}
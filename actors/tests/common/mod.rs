@@ -0,0 +1,160 @@
+//! Fixtures shared by the integration tests
+//!
+//! A plant in a feedback loop with an integral controller following a sinusoidal setpoint,
+//! the state of the plant being logged.
+
+#![allow(dead_code)]
+
+use gmt_dos_actors::{
+    model::{Model, Unknown},
+    prelude::*,
+};
+use gmt_dos_clients::{
+    interface::{Data, Read, Update, Write, UID},
+    Logging, Signal, Signals,
+};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+#[derive(UID)]
+pub enum Setpoint {}
+#[derive(UID)]
+pub enum Command {}
+#[derive(UID)]
+pub enum State {}
+
+/// First order plant: x[k+1] = a x[k] + u[k]
+#[derive(Default)]
+pub struct Plant {
+    x: f64,
+    u: f64,
+}
+impl Update for Plant {
+    fn update(&mut self) {
+        self.x = 0.9 * self.x + self.u;
+    }
+}
+impl Read<Command> for Plant {
+    fn read(&mut self, data: Data<Command>) {
+        self.u = data[0];
+    }
+}
+impl Write<State> for Plant {
+    fn write(&mut self) -> Option<Data<State>> {
+        Some(vec![self.x].into())
+    }
+}
+#[cfg(feature = "checkpoint")]
+impl gmt_dos_clients::interface::Checkpoint for Plant {
+    type State = (f64, f64);
+    fn state(&self) -> Self::State {
+        (self.x, self.u)
+    }
+    fn restore(&mut self, (x, u): Self::State) {
+        *self = Self { x, u };
+    }
+}
+
+/// Integral controller: u[k+1] = u[k] + g (r[k] - x[k])
+#[derive(Default)]
+pub struct Controller {
+    r: f64,
+    x: f64,
+    u: f64,
+}
+impl Update for Controller {
+    fn update(&mut self) {
+        self.u += 0.5 * (self.r - self.x);
+    }
+}
+impl Read<Setpoint> for Controller {
+    fn read(&mut self, data: Data<Setpoint>) {
+        self.r = data[0];
+    }
+}
+impl Read<State> for Controller {
+    fn read(&mut self, data: Data<State>) {
+        self.x = data[0];
+    }
+}
+impl Write<Command> for Controller {
+    fn write(&mut self) -> Option<Data<Command>> {
+        Some(vec![self.u].into())
+    }
+}
+#[cfg(feature = "checkpoint")]
+impl gmt_dos_clients::interface::Checkpoint for Controller {
+    type State = (f64, f64, f64);
+    fn state(&self) -> Self::State {
+        (self.r, self.x, self.u)
+    }
+    fn restore(&mut self, (r, x, u): Self::State) {
+        *self = Self { r, x, u };
+    }
+}
+
+pub type Logs = Arc<Mutex<Logging<f64>>>;
+
+/// Logging sink
+pub fn logger() -> Terminator<Logging<f64>> {
+    Terminator::<_>::new(Logging::<f64>::default().into_arcx())
+}
+
+/// Actors of the feedback loop
+pub struct Feedback {
+    pub setpoint: Initiator<Signals>,
+    pub controller: Actor<Controller>,
+    pub plant: Actor<Plant>,
+    pub logger: Terminator<Logging<f64>>,
+    pub logging: Logs,
+}
+impl Feedback {
+    /// Creates the actors for a setpoint of `n_step` samples
+    pub fn new(n_step: usize) -> Self {
+        let logging = Logging::<f64>::default().into_arcx();
+        Self {
+            setpoint: Signals::new(1, n_step)
+                .channels(Signal::Sinusoid {
+                    amplitude: 1.,
+                    sampling_frequency_hz: 50.,
+                    frequency_hz: 1.,
+                    phase_s: 0.,
+                })
+                .into(),
+            controller: Controller::default().into(),
+            plant: Plant::default().into(),
+            logger: Terminator::<_>::new(logging.clone()),
+            logging,
+        }
+    }
+    /// Connects the actors, bootstrapping the command of the controller
+    pub fn connect(mut self) -> anyhow::Result<Self> {
+        self.setpoint
+            .add_output()
+            .build::<Setpoint>()
+            .into_input(&mut self.controller)?;
+        self.controller
+            .add_output()
+            .bootstrap()
+            .build::<Command>()
+            .into_input(&mut self.plant)?;
+        self.plant
+            .add_output()
+            .multiplex(2)
+            .build::<State>()
+            .into_input(&mut self.controller)
+            .into_input(&mut self.logger)?;
+        Ok(self)
+    }
+    /// Returns the model and the logs of the plant state
+    pub fn into_model(self) -> (Model<Unknown>, Logs) {
+        let Self {
+            setpoint,
+            controller,
+            plant,
+            logger,
+            logging,
+        } = self;
+        (model!(setpoint, controller, plant, logger), logging)
+    }
+}
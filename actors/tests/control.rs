@@ -0,0 +1,88 @@
+use gmt_dos_actors::{model::ExitReason, prelude::*};
+use gmt_dos_clients::{interface::UID, Logging, Sampler, Signal, Signals};
+use std::time::Duration;
+
+#[derive(UID)]
+enum Ramp {}
+#[derive(UID)]
+enum Decimated {}
+
+#[tokio::test]
+async fn stopped_model_ends_on_whole_cycles() -> anyhow::Result<()> {
+    let mut source: Initiator<_> =
+        Actor::from(Signals::new(1, usize::MAX).channels(Signal::Ramp { a: 1., b: 0. }))
+            .name("source");
+    let mut sampler: Actor<_, 1, 10> =
+        Actor::from(Sampler::<Vec<f64>, Ramp, Decimated>::default()).name("sampler");
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut logger = Terminator::<_, 10>::new(logging.clone());
+
+    source
+        .add_output()
+        .build::<Ramp>()
+        .into_input(&mut sampler)?;
+    sampler
+        .add_output()
+        .build::<Decimated>()
+        .into_input(&mut logger)?;
+
+    let model = model!(source, sampler, logger).check()?.run();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    model.handle().stop();
+    let model = model.await?;
+
+    // the actors waiting on their inputs exit as the channels are closed
+    let report = model.report().unwrap();
+    assert!(report.is_normal());
+    assert_eq!(report.get("source").unwrap().exit, ExitReason::Stopped);
+    let n_cycle = report.get("sampler").unwrap().n_step / 10;
+
+    let data: Vec<f64> = logging.lock().await.iter().copied().collect();
+    assert!(!data.is_empty());
+    assert!(data.len() <= n_cycle);
+    assert!(data
+        .iter()
+        .enumerate()
+        .all(|(i, &x)| x == (10 * i + 9) as f64));
+    Ok(())
+}
+
+#[tokio::test]
+async fn paused_model_resumes() -> anyhow::Result<()> {
+    let build = || -> anyhow::Result<_> {
+        let mut source: Initiator<_> = Signals::new(1, 200)
+            .channels(Signal::Ramp { a: 1., b: 0. })
+            .into();
+        let mut sampler: Actor<_> = Sampler::<Vec<f64>, Ramp>::default().into();
+        let logging = Logging::<f64>::default().into_arcx();
+        let mut logger = Terminator::<_>::new(logging.clone());
+        source
+            .add_output()
+            .build::<Ramp>()
+            .into_input(&mut sampler)?;
+        sampler
+            .add_output()
+            .build::<Ramp>()
+            .into_input(&mut logger)?;
+        Ok((model!(source, sampler, logger), logging))
+    };
+
+    let (model, logging) = build()?;
+    let model = model.check()?.run();
+    model.handle().pause_at(100);
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while logging.lock().await.len() < 100 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+    })
+    .await?;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(model.handle().is_paused());
+    assert_eq!(logging.lock().await.len(), 100);
+    model.handle().resume();
+    model.await?;
+
+    let data: Vec<f64> = logging.lock().await.iter().copied().collect();
+    assert_eq!(data, (0..200).map(|i| i as f64).collect::<Vec<_>>());
+    Ok(())
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "loader")]
+
+use gmt_dos_actors::loader::{LoaderError, ModelDescription, Registry};
+use gmt_dos_clients::{interface::UID, Logging, Signals};
+
+#[derive(UID)]
+enum Source {}
+
+fn registry() -> Registry {
+    let mut registry = Registry::new();
+    registry
+        .client("Signals", |(n, n_step): (usize, usize)| {
+            Signals::new(n, n_step)
+        })
+        .output::<Source>();
+    registry
+        .client("Logging", |n: usize| Logging::<f64>::new(n))
+        .input::<Source>();
+    registry
+}
+
+fn description(connection: &str) -> anyhow::Result<ModelDescription> {
+    Ok(ModelDescription::from_toml(&format!(
+        r#"
+[[actors]]
+name = "source"
+client = "Signals"
+params = [1, 10]
+
+[[actors]]
+name = "sink"
+client = "Logging"
+params = 1
+
+[[connections]]
+from = "source"
+uid = "Source"
+to = ["sink"]
+{connection}
+"#
+    ))?)
+}
+
+#[test]
+fn invalid_capacities() -> anyhow::Result<()> {
+    for connection in ["capacity = 0", "unbounded = true\ncapacity = 10"] {
+        assert!(matches!(
+            registry().build(&description(connection)?),
+            Err(LoaderError::Capacity { .. })
+        ));
+    }
+    assert!(registry().build(&description("capacity = 10")?).is_ok());
+    Ok(())
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use common::{Feedback, Logs};
+use gmt_dos_actors::model::{Model, Unknown};
+
+fn feedback_model(blocking: bool) -> anyhow::Result<(Model<Unknown>, Logs)> {
+    let mut feedback = Feedback::new(200);
+    if blocking {
+        feedback.plant = feedback.plant.blocking();
+    }
+    Ok(feedback.connect()?.into_model())
+}
+
+#[test]
+fn lockstep_runs_are_identical() -> anyhow::Result<()> {
+    let (model, logging) = feedback_model(false)?;
+    let first = model.check()?.run_lockstep()?;
+    let first_data: Vec<f64> = logging.blocking_lock().iter().copied().collect();
+
+    let (model, logging) = feedback_model(false)?;
+    let second = model.check()?.run_lockstep()?;
+    let second_data: Vec<f64> = logging.blocking_lock().iter().copied().collect();
+
+    // the bootstrapped command steps the plant once more than the setpoint
+    assert_eq!(first_data.len(), 201);
+    assert_eq!(first.trace(), second.trace());
+    assert!(!first.trace().unwrap().is_empty());
+    assert_eq!(first_data, second_data);
+    Ok(())
+}
+
+#[test]
+fn lockstep_runs_blocking_actors() -> anyhow::Result<()> {
+    let (model, logging) = feedback_model(false)?;
+    model.check()?.run_lockstep()?;
+    let reference: Vec<f64> = logging.blocking_lock().iter().copied().collect();
+
+    // the plant client is called from the lockstep thread
+    let (model, logging) = feedback_model(true)?;
+    model.check()?.run_lockstep()?;
+    let blocking: Vec<f64> = logging.blocking_lock().iter().copied().collect();
+
+    assert_eq!(reference, blocking);
+    Ok(())
+}
+
+#[tokio::test]
+async fn lockstep_matches_multithreaded_run() -> anyhow::Result<()> {
+    let (model, logging) = feedback_model(false)?;
+    model.check()?.run().await?;
+    let threaded: Vec<f64> = logging.lock().await.iter().copied().collect();
+
+    let (model, logging) = feedback_model(false)?;
+    let model = model.check()?;
+    let lockstep = tokio::task::spawn_blocking(move || model.run_lockstep().map(|_| ()));
+    lockstep.await??;
+    let lockstep: Vec<f64> = logging.lock().await.iter().copied().collect();
+
+    assert_eq!(threaded, lockstep);
+    Ok(())
+}
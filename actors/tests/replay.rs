@@ -0,0 +1,85 @@
+#![cfg(feature = "replay")]
+
+mod common;
+
+use common::{logger, Command, Setpoint};
+use gmt_dos_actors::{
+    prelude::*,
+    replay::{Recorder, Recording, Replay, ReplayError},
+};
+use gmt_dos_clients::{
+    interface::{Data, Read, Update, Write},
+    Sampler, Signals,
+};
+
+// A client that has gone wrong
+struct Nan;
+impl Update for Nan {}
+impl Read<Setpoint> for Nan {
+    fn read(&mut self, _data: Data<Setpoint>) {}
+}
+impl Write<Command> for Nan {
+    fn write(&mut self) -> Option<Data<Command>> {
+        Some(vec![f64::NAN].into())
+    }
+}
+
+#[tokio::test]
+async fn nan_outputs_mismatch() -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join("nan_outputs_mismatch.bin");
+    let mut source: Initiator<_> = Signals::new(1, 10).into();
+    let mut sampler: Actor<_> = Sampler::<Vec<f64>, Setpoint, Command>::default().into();
+    let mut sink = logger();
+    source
+        .add_output()
+        .build::<Setpoint>()
+        .into_input(&mut sampler)?;
+    sampler
+        .add_output()
+        .build::<Command>()
+        .into_input(&mut sink)?;
+    let recorder = Recorder::new(&path)?.into_arcx();
+    source.record::<Setpoint>(&recorder)?;
+    sampler.record::<Command>(&recorder)?;
+    model!(source, sampler, sink).check()?.run().await?;
+    recorder.lock().await.flush()?;
+
+    let replay = Replay::new(Nan)
+        .input::<Setpoint>()
+        .output::<Command>(1e-12)
+        .run(&Recording::from_path(&path)?);
+    assert!(matches!(replay, Err(ReplayError::Mismatch { step: 0, .. })));
+    Ok(())
+}
+
+#[tokio::test]
+async fn outputs_recorded_separately() -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join("outputs_recorded_separately.bin");
+    let mut first: Initiator<_> = (Signals::new(1, 10), "first").into();
+    let mut second: Initiator<_> = (Signals::new(1, 10), "second").into();
+    let (mut first_sink, mut second_sink) = (logger(), logger());
+    first
+        .add_output()
+        .build::<Setpoint>()
+        .into_input(&mut first_sink)?;
+    second
+        .add_output()
+        .build::<Setpoint>()
+        .into_input(&mut second_sink)?;
+    let recorder = Recorder::new(&path)?.into_arcx();
+    first.record::<Setpoint>(&recorder)?;
+    second.record::<Setpoint>(&recorder)?;
+    model!(first, second, first_sink, second_sink)
+        .check()?
+        .run()
+        .await?;
+    recorder.lock().await.flush()?;
+
+    let recording = Recording::from_path(&path)?;
+    assert_eq!(recording.uids().len(), 2);
+    let replay = Replay::new(Sampler::<Vec<f64>, Setpoint, Command>::default())
+        .input::<Setpoint>()
+        .run(&recording);
+    assert!(matches!(replay, Err(ReplayError::Ambiguous(_, 2))));
+    Ok(())
+}
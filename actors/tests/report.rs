@@ -0,0 +1,118 @@
+use gmt_dos_actors::{
+    model::{ExitReason, Model, ModelError, Unknown},
+    prelude::*,
+};
+use gmt_dos_clients::{interface::UID, Logging, Sampler, Signals};
+use std::time::Duration;
+
+#[derive(UID)]
+enum Source {}
+
+fn model(n_step: usize) -> anyhow::Result<Model<Unknown>> {
+    let mut source: Initiator<_> = Actor::from(Signals::new(1, n_step)).name("source");
+    let mut sampler: Actor<_> = Actor::from(Sampler::<Vec<f64>, Source>::default()).name("sampler");
+    let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx()).name("sink");
+    source
+        .add_output()
+        .build::<Source>()
+        .into_input(&mut sampler)?;
+    sampler
+        .add_output()
+        .build::<Source>()
+        .into_input(&mut sink)?;
+    Ok(model!(source, sampler, sink))
+}
+
+#[tokio::test]
+async fn completed_model_reports_exit_reasons() -> anyhow::Result<()> {
+    let model = model(100)?.check()?.run().await?;
+    let report = model.report().unwrap();
+
+    let source = report.get("source").unwrap();
+    assert_eq!(source.exit, ExitReason::NoData);
+    // the last update finds the signals exhausted
+    assert_eq!(source.n_step, 101);
+    for name in ["sampler", "sink"] {
+        let actor = report.get(name).unwrap();
+        assert_eq!(actor.exit, ExitReason::DropRecv);
+        assert_eq!(actor.n_step, 100);
+        assert!(actor.channel.as_ref().unwrap().ends_with("Source"));
+    }
+    assert!(report.is_normal());
+    model.check()?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn aborted_model_reports_abnormal_exits() -> anyhow::Result<()> {
+    let model = model(usize::MAX)?.check()?.run();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    model.handle().abort();
+    let model = model.await?;
+    let report = model.report().unwrap();
+
+    assert!(report.iter().all(|actor| actor.exit == ExitReason::Aborted));
+    assert_eq!(report.abnormal().len(), 3);
+    let Err(ModelError::AbnormalExit(abnormal)) = model.check() else {
+        panic!("the aborted actors should be reported")
+    };
+    assert_eq!(abnormal.len(), 3);
+    Ok(())
+}
+
+mod a {
+    use gmt_dos_clients::interface::UniqueIdentifier;
+    pub struct Foo<U>(std::marker::PhantomData<U>);
+    impl<U: Send + Sync> UniqueIdentifier for Foo<U> {
+        type DataType = Vec<f64>;
+    }
+}
+mod b {
+    pub enum Bar {}
+    pub enum Baz {}
+}
+
+#[test]
+fn graph_names_generic_uids() -> anyhow::Result<()> {
+    let mut source: Initiator<_> = Signals::new(1, 10).into();
+    let mut bar = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+    let mut baz = Terminator::<_>::new(Logging::<f64>::default().into_arcx());
+    source
+        .add_output()
+        .build::<a::Foo<b::Bar>>()
+        .into_input(&mut bar)?;
+    source
+        .add_output()
+        .build::<a::Foo<b::Baz>>()
+        .into_input(&mut baz)?;
+    let json = model!(source, bar, baz).graph().unwrap().as_json();
+    assert!(json.contains(r#""uid": "Foo<Bar>""#));
+    assert!(json.contains(r#""uid": "Foo<Baz>""#));
+    Ok(())
+}
+
+#[derive(UID)]
+enum Monitored {}
+
+#[tokio::test]
+async fn dropped_oldest_output_ends_with_receiver() -> anyhow::Result<()> {
+    let mut source: Initiator<_> = Actor::from(Signals::new(1, 10)).name("source");
+    let mut monitored: Initiator<_> = Actor::from(Signals::new(1, usize::MAX)).name("monitored");
+    let mut sink = Terminator::<_>::new(Logging::<f64>::default().into_arcx()).name("sink");
+    source
+        .add_output()
+        .build::<Source>()
+        .into_input(&mut sink)?;
+    monitored
+        .add_output()
+        .capacity(1)
+        .overflow(Overflow::DropOldest)
+        .build::<Monitored>()
+        .into_input(&mut sink)?;
+    let model = model!(source, monitored, sink).check()?.run();
+    let model = tokio::time::timeout(Duration::from_secs(10), model).await??;
+
+    let report = model.report().unwrap();
+    assert_eq!(report.get("monitored").unwrap().exit, ExitReason::DropSend);
+    Ok(())
+}
@@ -0,0 +1,80 @@
+#![cfg(all(feature = "transport", unix))]
+
+mod common;
+
+use common::{logger, Setpoint};
+use gmt_dos_actors::{
+    model::{ExitReason, Model, Ready},
+    prelude::*,
+    transport::{Endpoint, IntoTransmitter, Receiver, Transmitter},
+};
+use gmt_dos_clients::Signals;
+use std::{any::type_name, path::PathBuf, time::Duration};
+use tokio::{io::AsyncWriteExt, net::UnixStream};
+
+fn socket(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("{name}-{}.sock", std::process::id()))
+}
+
+fn receiving_model(path: &PathBuf) -> anyhow::Result<Model<Ready>> {
+    let mut receiver = Receiver::<Setpoint>::new(Endpoint::unix(path)).max_frame_size(1024);
+    let mut sink = logger().name("sink");
+    receiver.into_input(&mut sink)?;
+    Ok(model!(receiver, sink).check()?)
+}
+
+// A foreign client on the receiver endpoint
+async fn connect(path: &PathBuf) -> anyhow::Result<UnixStream> {
+    loop {
+        match UnixStream::connect(path).await {
+            Ok(stream) => return Ok(stream),
+            Err(_) => tokio::time::sleep(Duration::from_millis(10)).await,
+        }
+    }
+}
+
+async fn receiver_exit(model: Model<Ready>) -> anyhow::Result<ExitReason> {
+    let model = model.run().await?;
+    let report = model.report().unwrap();
+    let receiver = report.iter().find(|actor| actor.name != "sink").unwrap();
+    Ok(receiver.exit.clone())
+}
+
+#[tokio::test]
+async fn oversized_frames() -> anyhow::Result<()> {
+    let path = socket("oversized_frames");
+    let receiving = tokio::spawn(receiver_exit(receiving_model(&path)?));
+    let mut stream = connect(&path).await?;
+    stream.write_u64_le(u64::MAX).await?;
+    let ExitReason::Other(error) = receiving.await?? else {
+        panic!("the receiver should reject the header")
+    };
+    assert!(error.contains("maximum frame size"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn closed_links() -> anyhow::Result<()> {
+    // the transmitter output is dropped
+    let path = socket("closed_links");
+    let receiving = tokio::spawn(receiver_exit(receiving_model(&path)?));
+    let mut source: Initiator<_> = Signals::new(1, 10).into();
+    let mut transmitter = Transmitter::<Setpoint>::new(Endpoint::unix(&path));
+    assert!(source
+        .add_output()
+        .build::<Setpoint>()
+        .into_transmitter(&mut transmitter)
+        .is_ok());
+    model!(source, transmitter).check()?.run().await?;
+    assert_eq!(receiving.await??, ExitReason::NoData);
+
+    // the transmitter closes the link without telling the receiver
+    let receiving = tokio::spawn(receiver_exit(receiving_model(&path)?));
+    let mut stream = connect(&path).await?;
+    let header = bincode::serialize(&(type_name::<Setpoint>(), 1usize))?;
+    stream.write_u64_le(header.len() as u64).await?;
+    stream.write_all(&header).await?;
+    drop(stream);
+    assert_eq!(receiving.await??, ExitReason::DropRecv);
+    Ok(())
+}
@@ -0,0 +1,242 @@
+mod common;
+
+use common::{logger, Command, Controller, Plant, Setpoint, State};
+use gmt_dos_actors::{model::ModelError, prelude::*, ActorError};
+use gmt_dos_clients::{Logging, Sampler, Signals};
+
+#[test]
+fn duplicated_connections() -> anyhow::Result<()> {
+    let mut source: Initiator<_> = Signals::new(1, 10).into();
+    let mut sink = logger();
+    source
+        .add_output()
+        .multiplex(2)
+        .build::<Setpoint>()
+        .into_input(&mut sink)
+        .into_input(&mut sink)?;
+    assert!(matches!(
+        model!(source, sink).check(),
+        Err(ModelError::DuplicatedConnections(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn missing_senders() -> anyhow::Result<()> {
+    let mut source: Initiator<_> = Signals::new(1, 10).into();
+    let mut sampler: Actor<_> = Sampler::<Vec<f64>, Setpoint>::default().into();
+    let mut sink = logger();
+    source
+        .add_output()
+        .build::<Setpoint>()
+        .into_input(&mut sampler)?;
+    sampler
+        .add_output()
+        .build::<Setpoint>()
+        .into_input(&mut sink)?;
+    let Err(ModelError::MissingSenders(missing)) = model!(sampler, sink).check() else {
+        panic!("the source is missing from the model")
+    };
+    assert_eq!(missing.len(), 1);
+    assert!(missing[0].contains("Setpoint"));
+    Ok(())
+}
+
+#[test]
+fn dangling_outputs() -> anyhow::Result<()> {
+    let mut source: Initiator<_> = Signals::new(1, 10).into();
+    let mut sampler: Actor<_> = Sampler::<Vec<f64>, Setpoint>::default().into();
+    let mut sink = logger();
+    source
+        .add_output()
+        .build::<Setpoint>()
+        .into_input(&mut sampler)?;
+    sampler
+        .add_output()
+        .build::<Setpoint>()
+        .into_input(&mut sink)?;
+    let Err(ModelError::DanglingOutputs(dangling)) = model!(source, sampler).check() else {
+        panic!("the sink is missing from the model")
+    };
+    assert_eq!(dangling.len(), 1);
+    assert!(dangling[0].contains("(0/1 inputs)"));
+    Ok(())
+}
+
+#[test]
+fn rate_mismatch() -> anyhow::Result<()> {
+    let mut source: Initiator<_> = Signals::new(1, 10).into();
+    let mut sink = DynActor::new(Logging::<f64>::default().into_arcx(), 10, 0);
+    source
+        .add_output()
+        .build::<Setpoint>()
+        .into_input(&mut sink)?;
+    let Err(ModelError::RateMismatch(mismatches)) = model!(source, sink).check() else {
+        panic!("the source and the sink rates differ")
+    };
+    assert_eq!(mismatches.len(), 1);
+    assert!(mismatches[0].contains("(rate 1)") && mismatches[0].contains("(rate 10)"));
+    Ok(())
+}
+
+#[test]
+fn reactor_plain_inputs() -> anyhow::Result<()> {
+    let mut source: Initiator<_> = Signals::new(1, 10).into();
+    let mut reactor: Reactor<_> = Sampler::<Vec<f64>, Setpoint, Command>::default().into();
+    let mut sink = logger();
+    // the input is added to the actor the reactor dereferences to
+    source
+        .add_output()
+        .build::<Setpoint>()
+        .into_input(&mut *reactor)?;
+    reactor
+        .add_output()
+        .build::<Command>()
+        .into_input(&mut sink)?;
+    assert!(matches!(
+        model!(source, reactor, sink).check(),
+        Err(ModelError::ActorIO(ActorError::NoEventInputs(_)))
+    ));
+    Ok(())
+}
+
+#[test]
+fn input_modes() -> anyhow::Result<()> {
+    let mut source: Initiator<_> = Signals::new(1, 100).into();
+    let mut decimator: Actor<_, 1, 10> = Sampler::<Vec<f64>, Setpoint>::default().into();
+    let mut sink = logger();
+    source
+        .add_output()
+        .multiplex(2)
+        .build::<Setpoint>()
+        .into_input(&mut decimator)
+        .into_input(&mut sink)?;
+    // only the decimated input does not wait for its data
+    decimator
+        .add_output()
+        .build::<Setpoint>()
+        .into_input_with_mode(&mut sink, InputMode::Optional)?;
+    model!(source, decimator, sink).check()?;
+    Ok(())
+}
+
+#[cfg(feature = "transport")]
+#[test]
+fn receiver_rates() -> anyhow::Result<()> {
+    use gmt_dos_actors::transport::{Endpoint, Receiver, TransportError};
+    let mut receiver = Receiver::<Setpoint>::new(Endpoint::tcp("127.0.0.1:0"));
+    let mut sink = logger();
+    let mut decimated_sink: Terminator<_, 10> = Logging::<f64>::default().into();
+    receiver.into_input(&mut sink)?;
+    let Err(TransportError::InputRate {
+        inputs_rate: 1,
+        input_rate: 10,
+    }) = receiver.into_input(&mut decimated_sink)
+    else {
+        panic!("the receiver inputs rates differ")
+    };
+    Ok(())
+}
+
+#[test]
+fn subsystem_exits() -> anyhow::Result<()> {
+    let mut source: Initiator<_> = Signals::new(1, 10).into();
+    let mut subsystem = SubSystem::new("Samplers");
+    subsystem
+        .add(Actor::<_>::from(
+            Sampler::<Vec<f64>, Setpoint, Command>::default(),
+        ))
+        .entry::<Setpoint>()
+        .exit::<Command>();
+    subsystem
+        .add(Actor::<_>::from(
+            Sampler::<Vec<f64>, State, Command>::default(),
+        ))
+        .entry::<State>()
+        .exit::<Command>();
+    let (mut first, mut second) = (logger(), logger());
+    source
+        .add_output()
+        .build::<Setpoint>()
+        .into_input(&mut subsystem)?;
+    source
+        .add_output()
+        .build::<State>()
+        .into_input(&mut subsystem)?;
+    // each exit is connected once
+    subsystem
+        .add_output()
+        .build::<Command>()
+        .into_input(&mut first)?;
+    subsystem
+        .add_output()
+        .build::<Command>()
+        .into_input(&mut second)?;
+    model!(source, subsystem, first, second).check()?;
+    Ok(())
+}
+
+fn decimation_model(n: usize) -> anyhow::Result<Model<model::Unknown>> {
+    let mut source: Initiator<_> = Signals::new(1, 10).into();
+    let mut sampler = DynActor::new(Sampler::<Vec<f64>, Setpoint>::default().into_arcx(), 1, n);
+    let mut sink: Terminator<_, 10> = Logging::<f64>::default().into();
+    source
+        .add_output()
+        .build::<Setpoint>()
+        .into_input(&mut sampler)?;
+    sampler
+        .add_output()
+        .build::<Setpoint>()
+        .into_input(&mut sink)?;
+    Ok(model!(source, sampler, sink))
+}
+
+#[test]
+fn dyn_actor_rates() -> anyhow::Result<()> {
+    decimation_model(10)?.check()?;
+    let Err(ModelError::RateMismatch(mismatches)) = decimation_model(5)?.check() else {
+        panic!("the sampler and the sink rates differ")
+    };
+    assert_eq!(mismatches.len(), 1);
+    assert!(mismatches[0].contains("(rate 5)") && mismatches[0].contains("(rate 10)"));
+    Ok(())
+}
+
+fn feedback_model<const NO: usize>(bootstrap: bool) -> anyhow::Result<Model<model::Unknown>> {
+    let mut setpoint: Initiator<_> = Signals::new(1, 10).into();
+    let mut controller: Actor<_, 1, NO> = Controller::default().into();
+    let mut plant: Actor<_, NO, 1> = Plant::default().into();
+    setpoint
+        .add_output()
+        .build::<Setpoint>()
+        .into_input(&mut controller)?;
+    let output = controller.add_output();
+    let output = if bootstrap {
+        output.bootstrap()
+    } else {
+        output
+    };
+    output.build::<Command>().into_input(&mut plant)?;
+    plant
+        .add_output()
+        .build::<State>()
+        .into_input(&mut controller)?;
+    Ok(model!(setpoint, controller, plant))
+}
+
+#[test]
+fn unbootstrapped_loops() -> anyhow::Result<()> {
+    let Err(ModelError::UnbootstrappedLoops(loops)) = feedback_model::<1>(false)?.check() else {
+        panic!("the feedback loop is not bootstrapped")
+    };
+    assert_eq!(loops.len(), 1);
+    assert!(loops[0].contains("bootstrap"));
+    Ok(())
+}
+
+#[test]
+fn bootstrapped_loops() -> anyhow::Result<()> {
+    feedback_model::<1>(true)?.check()?;
+    feedback_model::<10>(true)?.check()?;
+    Ok(())
+}
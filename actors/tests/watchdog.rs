@@ -0,0 +1,70 @@
+use gmt_dos_actors::{model::ModelError, prelude::*};
+use gmt_dos_clients::{
+    interface::{Data, Read, Update, UID},
+    Logging, Signals,
+};
+use std::time::Duration;
+
+#[derive(UID)]
+enum Source {}
+
+/// Client that hangs the first time it is updated
+#[derive(Default)]
+struct Hang(usize);
+impl Update for Hang {
+    fn update(&mut self) {
+        if self.0 == 0 {
+            std::thread::sleep(Duration::from_millis(500));
+        }
+        self.0 += 1;
+    }
+}
+impl Read<Source> for Hang {
+    fn read(&mut self, _data: Data<Source>) {}
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn stalled_model_is_aborted() -> anyhow::Result<()> {
+    let mut source: Initiator<_> = Actor::from(Signals::new(1, 1_000)).name("source");
+    let mut sink: Terminator<_> = Actor::from(Hang::default()).name("sink");
+    source
+        .add_output()
+        .build::<Source>()
+        .into_input(&mut sink)?;
+
+    let model = model!(source, sink)
+        .check()?
+        .run()
+        .watchdog(Duration::from_millis(50));
+    // the watchdog does not wait for the pause step to watch the model
+    model.handle().pause_at(500);
+    let Err(ModelError::Stalled(dump)) = model.await else {
+        panic!("the model should have been stalled")
+    };
+    assert!(dump.contains("source"));
+    assert!(dump.contains("sending"));
+    assert!(dump.contains("sink"));
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn paused_model_is_not_aborted() -> anyhow::Result<()> {
+    let mut source: Initiator<_> = Signals::new(1, 100).into();
+    let logging = Logging::<f64>::default().into_arcx();
+    let mut sink = Terminator::<_>::new(logging.clone());
+    source
+        .add_output()
+        .build::<Source>()
+        .into_input(&mut sink)?;
+
+    let model = model!(source, sink)
+        .check()?
+        .run()
+        .watchdog(Duration::from_millis(20));
+    model.handle().pause_at(10);
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    model.handle().resume();
+    model.await?.check()?;
+    assert_eq!(logging.lock().await.len(), 100);
+    Ok(())
+}
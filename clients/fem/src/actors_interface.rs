@@ -38,6 +38,32 @@ where
     }
 }
 
+#[cfg(feature = "checkpoint")]
+impl<S> gmt_dos_clients::interface::Checkpoint for DiscreteModalSolver<S>
+where
+    S: crate::SolverState + Default,
+{
+    /// Inputs, outputs, static gain correction and state vectors
+    type State = (Vec<f64>, Vec<f64>, Vec<f64>, Vec<(f64, f64)>);
+    fn state(&self) -> Self::State {
+        (
+            self.u.clone(),
+            self.y.clone(),
+            self.psi_times_u.clone(),
+            self.state_space.iter().map(|ss| ss.state()).collect(),
+        )
+    }
+    fn restore(&mut self, (u, y, psi_times_u, x): Self::State) {
+        self.u = u;
+        self.y = y;
+        self.psi_times_u = psi_times_u;
+        self.state_space
+            .iter_mut()
+            .zip(x)
+            .for_each(|(ss, x)| ss.set_state(x));
+    }
+}
+
 /* impl<S, U: UniqueIdentifier<DataType = Vec<f64>>> Read<U> for DiscreteModalSolver<S>
 where
     Vec<Option<fem_io::Inputs>>: fem_io::FemIo<U>,
@@ -48,3 +48,12 @@ impl super::Solver for Bilinear {
         self.y.as_slice()
     }
 }
+#[cfg(feature = "checkpoint")]
+impl super::SolverState for Bilinear {
+    fn state(&self) -> (f64, f64) {
+        self.x
+    }
+    fn set_state(&mut self, x: (f64, f64)) {
+        self.x = x;
+    }
+}
@@ -160,6 +160,15 @@ impl super::Solver for Exponential {
         self.y.as_slice()
     }
 }
+#[cfg(feature = "checkpoint")]
+impl super::SolverState for Exponential {
+    fn state(&self) -> (f64, f64) {
+        self.x
+    }
+    fn set_state(&mut self, x: (f64, f64)) {
+        self.x = x;
+    }
+}
 impl fmt::Display for Exponential {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
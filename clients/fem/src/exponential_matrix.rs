@@ -158,6 +158,15 @@ impl super::Solver for ExponentialMatrix {
         self.y.as_slice()
     }
 }
+#[cfg(feature = "checkpoint")]
+impl super::SolverState for ExponentialMatrix {
+    fn state(&self) -> (f64, f64) {
+        self.x
+    }
+    fn set_state(&mut self, x: (f64, f64)) {
+        self.x = x;
+    }
+}
 impl fmt::Display for ExponentialMatrix {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
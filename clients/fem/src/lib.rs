@@ -55,6 +55,14 @@ pub trait Solver {
     ) -> Self;
     fn solve(&mut self, u: &[f64]) -> &[f64];
 }
+/// State vector of a [Solver], saved in the checkpoints of the [DiscreteModalSolver]
+#[cfg(feature = "checkpoint")]
+pub trait SolverState: Solver {
+    /// Returns the state vector
+    fn state(&self) -> (f64, f64);
+    /// Sets the state vector
+    fn set_state(&mut self, x: (f64, f64));
+}
 
 #[derive(Debug, thiserror::Error)]
 pub enum StateSpaceError {
@@ -113,6 +113,9 @@ pub use logging::Logging;
 mod sampler;
 #[doc(inline)]
 pub use sampler::Sampler;
+mod resampler;
+#[doc(inline)]
+pub use resampler::{Interpolation, Resampler};
 mod pulse;
 #[doc(inline)]
 pub use pulse::Pulse;
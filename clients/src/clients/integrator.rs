@@ -119,3 +119,17 @@ where
         Some(Data::new(y))
     }
 }
+#[cfg(feature = "checkpoint")]
+impl<T, U> crate::interface::Checkpoint for Integrator<U>
+where
+    T: Clone + serde::Serialize + serde::de::DeserializeOwned,
+    U: UniqueIdentifier<DataType = Vec<T>>,
+{
+    type State = Vec<T>;
+    fn state(&self) -> Self::State {
+        self.mem.clone()
+    }
+    fn restore(&mut self, state: Self::State) {
+        self.mem = state;
+    }
+}
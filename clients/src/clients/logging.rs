@@ -1,14 +1,17 @@
 use super::{Data, Read, UniqueIdentifier, Update};
+use crate::interface::Stamp;
 use std::fmt::Display;
 
 /// Simple data logging
 ///
 /// Accumulates all the inputs in a single [Vec]
+/// and the [Stamp] of the first entry of each time sample
 #[derive(Debug)]
 pub struct Logging<T> {
     data: Vec<T>,
     n_sample: usize,
     n_entry: usize,
+    stamps: Vec<Stamp>,
 }
 
 impl<T> std::ops::Deref for Logging<T> {
@@ -24,6 +27,7 @@ impl<T> Default for Logging<T> {
             n_entry: 1,
             data: Vec::new(),
             n_sample: 0,
+            stamps: Vec::new(),
         }
     }
 }
@@ -59,6 +63,12 @@ impl<T> Logging<T> {
     pub fn is_empty(&self) -> bool {
         self.n_sample == 0
     }
+    /// Returns the stamps of the time samples
+    ///
+    /// The stamps are only available if the data comes from an actor output
+    pub fn stamps(&self) -> &[Stamp] {
+        &self.stamps
+    }
     /// Returns data chunks the size of the entries
     pub fn chunks(&self) -> impl Iterator<Item = &[T]> {
         self.data.chunks(self.n_data())
@@ -95,7 +105,28 @@ impl<T> Update for Logging<T> {}
 impl<T: Clone, U: UniqueIdentifier<DataType = Vec<T>>> Read<U> for Logging<T> {
     fn read(&mut self, data: Data<U>) {
         // log::debug!("receive {} input: {:}", type_name::<U>(), data.len(),);
+        if let Some(stamp) = data
+            .stamp()
+            .filter(|_| self.n_sample.is_multiple_of(self.n_entry))
+        {
+            self.stamps.push(*stamp);
+        }
         self.data.extend((&**data).to_vec());
         self.n_sample += 1;
     }
 }
+#[cfg(feature = "checkpoint")]
+impl<T> crate::interface::Checkpoint for Logging<T>
+where
+    T: Clone + serde::Serialize + serde::de::DeserializeOwned,
+{
+    type State = (Vec<T>, usize, Vec<Stamp>);
+    fn state(&self) -> Self::State {
+        (self.data.clone(), self.n_sample, self.stamps.clone())
+    }
+    fn restore(&mut self, (data, n_sample, stamps): Self::State) {
+        self.data = data;
+        self.n_sample = n_sample;
+        self.stamps = stamps;
+    }
+}
@@ -0,0 +1,174 @@
+use super::{Data, Read, UniqueIdentifier, Update, Write};
+use std::{collections::VecDeque, f64::consts::PI, marker::PhantomData, sync::Arc};
+
+/// [Resampler] interpolation methods
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Interpolation {
+    /// Holds the last input sample
+    ZeroOrderHold,
+    /// Linear interpolation between 2 input samples, delays the output by 1 input sample
+    Linear,
+    /// Windowed sinc interpolation over the given even number of input samples,
+    /// delays the output by half the number of samples
+    Polyphase(usize),
+}
+
+/// Rational rate transitionner
+///
+/// The resampler must be the client of an actor with the same inputs and outputs rates
+/// than the ones given to [Resampler::new], e.g. for a 3:2 rate transition:
+/// ```ignore
+/// let resampler: Actor<_, 3, 2> = Resampler::<U, V>::new(3, 2, Interpolation::Linear).into();
+/// ```
+/// An input sample is received every `ni` time steps and an output sample is sent every `no` time steps.
+#[derive(Debug)]
+pub struct Resampler<U, V = U>
+where
+    U: UniqueIdentifier<DataType = Vec<f64>>,
+    V: UniqueIdentifier<DataType = Vec<f64>>,
+{
+    ni: usize,
+    no: usize,
+    interpolation: Interpolation,
+    // input samples, the first one is sample # `first`
+    samples: VecDeque<Arc<Vec<f64>>>,
+    first: usize,
+    // # of output samples
+    n_out: usize,
+    input: PhantomData<U>,
+    output: PhantomData<V>,
+}
+impl<U, V> Resampler<U, V>
+where
+    U: UniqueIdentifier<DataType = Vec<f64>>,
+    V: UniqueIdentifier<DataType = Vec<f64>>,
+{
+    /// Creates a new resampler from the inputs and outputs rates
+    pub fn new(ni: usize, no: usize, interpolation: Interpolation) -> Self {
+        assert!(ni > 0 && no > 0, "resampler rates must be positive");
+        if let Interpolation::Polyphase(n) = interpolation {
+            assert!(
+                n > 0 && n % 2 == 0,
+                "the number of polyphase samples must be even"
+            );
+        }
+        Self {
+            ni,
+            no,
+            interpolation,
+            samples: VecDeque::new(),
+            first: 0,
+            n_out: 0,
+            input: PhantomData,
+            output: PhantomData,
+        }
+    }
+    // input sample # k, clamped to the samples available
+    fn sample(&self, k: isize) -> &[f64] {
+        let i = (k - self.first as isize).clamp(0, self.samples.len() as isize - 1);
+        &self.samples[i as usize]
+    }
+    // weighted sum of the input samples
+    fn weighted(&self, weights: &[(isize, f64)]) -> Vec<f64> {
+        let mut y = vec![0f64; self.sample(weights[0].0).len()];
+        for &(k, w) in weights {
+            y.iter_mut()
+                .zip(self.sample(k))
+                .for_each(|(y, x)| *y += w * x);
+        }
+        y
+    }
+}
+impl<U, V> Update for Resampler<U, V>
+where
+    U: UniqueIdentifier<DataType = Vec<f64>>,
+    V: UniqueIdentifier<DataType = Vec<f64>>,
+{
+}
+impl<U, V> Read<U> for Resampler<U, V>
+where
+    U: UniqueIdentifier<DataType = Vec<f64>>,
+    V: UniqueIdentifier<DataType = Vec<f64>>,
+{
+    fn read(&mut self, data: Data<U>) {
+        self.samples.push_back(data.into_arc());
+    }
+}
+impl<U, V> Write<V> for Resampler<U, V>
+where
+    U: UniqueIdentifier<DataType = Vec<f64>>,
+    V: UniqueIdentifier<DataType = Vec<f64>>,
+{
+    fn write(&mut self) -> Option<Data<V>> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        // output sample time in units of input samples
+        let time = (self.n_out * self.no) as f64 / self.ni as f64;
+        self.n_out += 1;
+        let (y, oldest) = match self.interpolation {
+            Interpolation::ZeroOrderHold => {
+                let k = self.first + self.samples.len() - 1;
+                (self.sample(k as isize).to_vec(), k)
+            }
+            Interpolation::Linear => {
+                let time = time - 1f64;
+                let k = time.floor();
+                let a = time - k;
+                let k = k as isize;
+                (
+                    self.weighted(&[(k, 1f64 - a), (k + 1, a)]),
+                    k.max(0) as usize,
+                )
+            }
+            Interpolation::Polyphase(n) => {
+                let h = (n / 2) as isize;
+                let time = time - h as f64;
+                let k0 = time.floor() as isize;
+                // anti-aliasing cut-off frequency when decimating
+                let fc = (self.ni as f64 / self.no as f64).min(1f64);
+                let mut weights: Vec<_> = (k0 - h + 1..=k0 + h)
+                    .map(|k| {
+                        let u = time - k as f64;
+                        let sinc = if u == 0f64 {
+                            1f64
+                        } else {
+                            (PI * fc * u).sin() / (PI * fc * u)
+                        };
+                        let hann = 0.5 * (1f64 + (PI * u / h as f64).cos());
+                        (k, fc * sinc * hann)
+                    })
+                    .collect();
+                let sum: f64 = weights.iter().map(|(_, w)| w).sum();
+                weights.iter_mut().for_each(|(_, w)| *w /= sum);
+                (self.weighted(&weights), (k0 - h + 1).max(0) as usize)
+            }
+        };
+        // the samples older than the oldest sample needed next time are discarded
+        while self.first < oldest && self.samples.len() > 1 {
+            self.samples.pop_front();
+            self.first += 1;
+        }
+        Some(Data::new(y))
+    }
+}
+#[cfg(feature = "checkpoint")]
+impl<U, V> crate::interface::Checkpoint for Resampler<U, V>
+where
+    U: UniqueIdentifier<DataType = Vec<f64>>,
+    V: UniqueIdentifier<DataType = Vec<f64>>,
+{
+    type State = (Vec<Vec<f64>>, usize, usize);
+    fn state(&self) -> Self::State {
+        (
+            self.samples.iter().map(|sample| sample.to_vec()).collect(),
+            self.first,
+            self.n_out,
+        )
+    }
+    fn restore(&mut self, (samples, first, n_out): Self::State) {
+        self.samples = samples.into_iter().map(Arc::new).collect();
+        self.first = first;
+        self.n_out = n_out;
+    }
+}
@@ -58,3 +58,18 @@ impl<T: Clone, U: UniqueIdentifier<DataType = T>, V: UniqueIdentifier<DataType =
         Some(Data::<V>::from(&self.data))
     }
 }
+#[cfg(feature = "checkpoint")]
+impl<T, U, V> crate::interface::Checkpoint for Sampler<T, U, V>
+where
+    T: Clone + serde::Serialize + serde::de::DeserializeOwned,
+    U: UniqueIdentifier<DataType = T>,
+    V: UniqueIdentifier<DataType = T>,
+{
+    type State = T;
+    fn state(&self) -> Self::State {
+        self.data.as_ref().clone()
+    }
+    fn restore(&mut self, state: Self::State) {
+        self.data = Arc::new(state);
+    }
+}
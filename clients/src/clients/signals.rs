@@ -201,6 +201,17 @@ impl<U: UniqueIdentifier<DataType = Vec<f64>>> Write<U> for Signals {
     }
 }
 
+#[cfg(feature = "checkpoint")]
+impl crate::interface::Checkpoint for Signals {
+    type State = usize;
+    fn state(&self) -> Self::State {
+        self.step
+    }
+    fn restore(&mut self, step: Self::State) {
+        self.step = step;
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum SignalsError {
     #[error("Two many signal channels, should be only 1")]
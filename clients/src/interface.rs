@@ -1,7 +1,7 @@
 use std::any::type_name;
 
 mod data;
-pub use data::Data;
+pub use data::{Data, Stamp};
 pub use dos_uid_derive::UID;
 
 pub type Assoc<U> = <U as UniqueIdentifier>::DataType;
@@ -22,6 +22,14 @@ pub trait Read<U: UniqueIdentifier> {
     /// Read data from an input
     fn read(&mut self, data: Data<U>);
 }
+/// Client input event reader interface
+///
+/// The data received by an event-driven actor on any of its inputs is read with [ReadEvent::read_event]
+/// and the actor updates the client and writes its outputs only if the client reacts to the event.
+pub trait ReadEvent<U: UniqueIdentifier> {
+    /// Reads the data of an event and returns `true` if the client must be updated
+    fn read_event(&mut self, data: Data<U>) -> bool;
+}
 /// Client output data writer interface
 pub trait Write<U: UniqueIdentifier> {
     fn write(&mut self) -> Option<Data<U>>;
@@ -69,3 +77,19 @@ pub trait Entry<U: UniqueIdentifier> {
     /// Adds an entry to the logger
     fn entry(&mut self, size: usize);
 }
+
+/// Client state checkpointing interface
+///
+/// The state of a client is saved in a checkpoint of a running model
+/// and it is restored when the model is resumed from the checkpoint.
+/// The state must include anything the client needs to produce the same outputs after being restored,
+/// including the data received from the inputs that has not yet been processed.
+#[cfg(feature = "checkpoint")]
+pub trait Checkpoint {
+    /// Client state
+    type State: serde::Serialize + serde::de::DeserializeOwned;
+    /// Returns the client state
+    fn state(&self) -> Self::State;
+    /// Restores the client state
+    fn restore(&mut self, state: Self::State);
+}
@@ -2,9 +2,24 @@ use std::{fmt, marker::PhantomData, ops::Deref, sync::Arc};
 
 use super::{UniqueIdentifier, Who};
 
+/// [Data] time stamp
+///
+/// The stamp is set by the actors outputs when the data is sent
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stamp {
+    /// Simulation step at which the data has been produced
+    pub step: usize,
+    /// Simulation time in seconds, if the sampling frequency of the model is known
+    pub time: Option<f64>,
+    /// Identifier of the actor that has produced the data
+    pub producer: u64,
+}
+
 /// Actors I/O data wrapper
 ///
 /// `U` is the data unique identifier (UID).
+/// The data may also carry a [Stamp] with the simulation step and time at which it has been produced.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Data<U: UniqueIdentifier>(
     #[cfg_attr(
@@ -17,6 +32,7 @@ pub struct Data<U: UniqueIdentifier>(
     )]
     Arc<<U as UniqueIdentifier>::DataType>,
     PhantomData<U>,
+    Option<Stamp>,
 );
 impl<T, U: UniqueIdentifier<DataType = T>> Deref for Data<U> {
     type Target = T;
@@ -32,18 +48,18 @@ unsafe impl<T: Sync, U: UniqueIdentifier<DataType = T>> Sync for Data<U> {}
 impl<T, U: UniqueIdentifier<DataType = T>> Clone for Data<U> {
     /// Makes a clone of the inner `Arc` pointer, returning a new instance of `Data<U>` with the cloned [Arc] within
     fn clone(&self) -> Self {
-        Self(Arc::clone(&self.0), PhantomData)
+        Self(Arc::clone(&self.0), PhantomData, self.2)
     }
 }
 
 impl<T, U: UniqueIdentifier<DataType = T>> Data<U> {
     /// Moves `data` into an `Arc` pointer and places into `Data<U>`
     pub fn new(data: T) -> Self {
-        Data(Arc::new(data), PhantomData)
+        Data(Arc::new(data), PhantomData, None)
     }
     /// Consumes `Data<U>`, returning `Data<V>` with the wrapped value within
     pub fn transmute<V: UniqueIdentifier<DataType = T>>(self) -> Data<V> {
-        Data(self.0, PhantomData)
+        Data(self.0, PhantomData, self.2)
     }
     /// Consumes `Data<U>`, returning the inner [Arc] pointer
     pub fn into_arc(self) -> Arc<T> {
@@ -53,6 +69,14 @@ impl<T, U: UniqueIdentifier<DataType = T>> Data<U> {
     pub fn as_arc(&self) -> Arc<T> {
         Arc::clone(&self.0)
     }
+    /// Returns the data [Stamp], if any
+    pub fn stamp(&self) -> Option<&Stamp> {
+        self.2.as_ref()
+    }
+    /// Consumes `Data<U>`, returning it with the given [Stamp]
+    pub fn with_stamp(self, stamp: Stamp) -> Self {
+        Data(self.0, PhantomData, Some(stamp))
+    }
 }
 impl<T, U> From<Data<U>> for Vec<T>
 where
@@ -78,18 +102,18 @@ impl<'a, T, U: UniqueIdentifier<DataType = Vec<T>>> From<&'a Data<U>> for &'a [T
 }
 impl<T, U: UniqueIdentifier<DataType = Vec<T>>> From<Vec<T>> for Data<U> {
     fn from(u: Vec<T>) -> Self {
-        Data(Arc::new(u), PhantomData)
+        Data(Arc::new(u), PhantomData, None)
     }
 }
 impl<T, U: UniqueIdentifier<DataType = T>> From<Arc<T>> for Data<U> {
     fn from(u: Arc<T>) -> Self {
-        Data(u, PhantomData)
+        Data(u, PhantomData, None)
     }
 }
 impl<T, U: UniqueIdentifier<DataType = T>> From<&Arc<T>> for Data<U> {
     /// Makes a clone of the `Arc` pointer, returning `Data<U>` with the cloned [Arc] within
     fn from(u: &Arc<T>) -> Self {
-        Data(Arc::clone(u), PhantomData)
+        Data(Arc::clone(u), PhantomData, None)
     }
 }
 impl<T, U, V> From<&Data<V>> for Data<U>
@@ -99,7 +123,7 @@ where
 {
     /// Makes a clone of `Data<V>` inner `Arc` pointer, returning `Data<U>` with the cloned [Arc] within
     fn from(data: &Data<V>) -> Self {
-        Data(Arc::clone(&data.0), PhantomData)
+        Data(Arc::clone(&data.0), PhantomData, data.2)
     }
 }
 impl<U: UniqueIdentifier> Who<U> for Data<U> {}
@@ -109,12 +133,16 @@ where
     U: UniqueIdentifier<DataType = T>,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_tuple("Data").field(&self.0).field(&self.1).finish()
+        f.debug_tuple("Data")
+            .field(&self.0)
+            .field(&self.1)
+            .field(&self.2)
+            .finish()
     }
 }
 impl<T: Default, U: UniqueIdentifier<DataType = T>> Default for Data<U> {
     fn default() -> Self {
-        Self(Default::default(), Default::default())
+        Self(Default::default(), Default::default(), None)
     }
 }
 impl<T, U> PartialEq for Data<U>
@@ -122,6 +150,7 @@ where
     T: PartialEq,
     U: UniqueIdentifier<DataType = T>,
 {
+    /// Compares the data, ignoring the [Stamp]s
     fn eq(&self, other: &Self) -> bool {
         self.0 == other.0 && self.1 == other.1
     }
@@ -2,7 +2,8 @@
 mod clients;
 #[cfg(feature = "clients")]
 pub use clients::{
-    Average, Integrator, Logging, Pulse, Sampler, Signal, Signals, Smooth, Source, Tick, Timer,
+    Average, Integrator, Interpolation, Logging, Pulse, Resampler, Sampler, Signal, Signals,
+    Smooth, Source, Tick, Timer,
 };
 #[cfg(feature = "interface")]
 pub mod interface;
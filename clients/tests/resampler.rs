@@ -0,0 +1,62 @@
+use gmt_dos_clients::{
+    interface::{Data, Read, Write, UID},
+    Interpolation, Resampler,
+};
+
+#[derive(UID)]
+enum In {}
+#[derive(UID)]
+enum Out {}
+
+/// Steps a 3:2 resampler as an `Actor<_, 3, 2>` does,
+/// reading an input sample every 3 steps and writing an output sample every 2 steps
+fn resample(interpolation: Interpolation, input: impl Fn(usize) -> f64, n: usize) -> Vec<f64> {
+    let mut resampler = Resampler::<In, Out>::new(3, 2, interpolation);
+    let mut output = vec![];
+    for step in 0.. {
+        if step % 3 == 0 {
+            resampler.read(Data::new(vec![input(step / 3)]));
+        }
+        if (step + 1) % 2 == 0 {
+            let data: Data<Out> = resampler.write().unwrap();
+            output.push(data[0]);
+            if output.len() == n {
+                break;
+            }
+        }
+    }
+    output
+}
+
+fn assert_close(actual: &[f64], expected: &[f64]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 1e-12, "{actual:?} != {expected:?}");
+    }
+}
+
+#[test]
+fn zero_order_hold() {
+    let output = resample(Interpolation::ZeroOrderHold, |k| k as f64, 9);
+    assert_close(&output, &[0., 1., 1., 2., 3., 3., 4., 5., 5.]);
+}
+
+#[test]
+fn linear() {
+    // the ramp is delayed by 1 input sample
+    let output = resample(Interpolation::Linear, |k| k as f64, 9);
+    let expected: Vec<_> = (0..9).map(|j| (2. * j as f64 / 3. - 1.).max(0.)).collect();
+    assert_close(&output, &expected);
+}
+
+#[test]
+fn polyphase() {
+    // unit DC gain
+    let output = resample(Interpolation::Polyphase(4), |_| 1.5, 12);
+    assert_close(&output, &[1.5; 12]);
+    // the output samples aligned with the input samples are the input samples,
+    // delayed by 2 input samples
+    let output = resample(Interpolation::Polyphase(4), |k| k as f64, 15);
+    let aligned: Vec<_> = output.into_iter().step_by(3).collect();
+    assert_close(&aligned, &[0., 0., 2., 4., 6.]);
+}